@@ -15,6 +15,8 @@ pub fn plugin(app: &mut App) {
         .load_resource_from_path::<Config>("config.ron")
         .add_plugins(RonAssetPlugin::<Credits>::default())
         .load_resource_from_path::<Credits>("credits.ron")
+        .add_plugins(RonAssetPlugin::<Soundtrack>::default())
+        .load_resource_from_path::<Soundtrack>("soundtrack.ron")
         .load_resource::<AudioSources>()
         .load_resource::<Textures>()
         // .load_resource::<Fonts>()
@@ -94,12 +96,6 @@ pub struct AudioSources {
     pub btn_press: Handle<Sample>,
     #[dependency]
     pub steps: Vec<Handle<Sample>>,
-
-    // music
-    #[dependency]
-    pub explore: Vec<Handle<Sample>>,
-    #[dependency]
-    pub combat: Vec<Handle<Sample>>,
 }
 
 impl AudioSources {
@@ -113,20 +109,14 @@ impl AudioSources {
         "audio/sfx/step3.ogg",
         "audio/sfx/step4.ogg",
     ];
-    pub const EXPLORE: &[&'static str] = &["audio/music/smnbl-time-for-fun.ogg"];
-    pub const COMBAT: &[&'static str] = &["audio/music/smnbl-trouble.ogg"];
 }
 
 impl FromWorld for AudioSources {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
         let steps = Self::STEPS.iter().map(|p| assets.load(*p)).collect();
-        let explore = Self::EXPLORE.iter().map(|p| assets.load(*p)).collect();
-        let combat = Self::COMBAT.iter().map(|p| assets.load(*p)).collect();
         Self {
             steps,
-            combat,
-            explore,
             btn_hover: assets.load(Self::BTN_HOVER),
             btn_press: assets.load(Self::BTN_PRESS),
         }
@@ -2,24 +2,26 @@
 //!
 //! [Music sampler pool](Music)
 //! [Sfx sampler pool](Sfx)
+//! [Ambience sampler pool](Ambience)
+//! [Ui sampler pool](Ui)
 //!
 //! ```text
-//! ┌─────┐┌───┐┌───────────┐
-//! │Music││Sfx││DefaultPool│
-//! └┬────┘└┬──┘└┬──────────┘
-//! ┌▽──────▽────▽┐
-//! │MainBus      │
-//! └─────────────┘
+//! ┌─────┐┌───┐┌────────┐┌──┐┌───────────┐
+//! │Music││Sfx││Ambience││Ui││DefaultPool│
+//! └┬────┘└┬──┘└┬───────┘└┬─┘└┬──────────┘
+//! ┌▽──────▽────▽─────────▽───▽┐
+//! │MainBus                    │
+//! └───────────────────────────┘
 //! ```
 //!
-//! The `Music` pool, `Sfx` pool, and `DefaultPool` are all routed to the `MainBus` node.
+//! The pools are all routed to the `MainBus` node.
 //! Since each pool has a `VolumeNode`, we can control them all individually. And,
 //! since they're all routed to the `MainBus`, we can also set the volume of all three
 //! at once.
 //!
-//! You can see this in action in the knob observers: to set the master volume,
+//! You can see this in action in [`apply_volumes`]: to set the master volume,
 //! we adjust the `MainBus` node, and to set the individual volumes, we adjust the
-//! pool nodes.
+//! pool nodes. Muting silences every pool.
 //!
 //! # Example
 //! ```rust,no_run
@@ -39,6 +41,7 @@
 //! }
 //! ```
 //!
+use crate::models::{GameState, Settings};
 use bevy::prelude::*;
 use bevy_seedling::{pool::SamplerPool, prelude::*};
 
@@ -56,7 +59,13 @@ pub fn plugin(app: &mut App) {
     // #[cfg(not(target_arch = "wasm32"))]
     app.add_plugins(bevy_seedling::SeedlingPlugin::default());
 
-    app.add_systems(Startup, spawn_pools);
+    app.add_systems(Startup, spawn_pools).add_systems(
+        Update,
+        apply_volumes.run_if(
+            resource_exists::<Settings>
+                .and(resource_changed::<Settings>.or(resource_changed::<GameState>)),
+        ),
+    );
 }
 
 fn spawn_pools(mut master: Single<&mut VolumeNode, With<MainBus>>, mut cmds: Commands) {
@@ -68,6 +77,8 @@ fn spawn_pools(mut master: Single<&mut VolumeNode, With<MainBus>>, mut cmds: Com
         VolumeNode {
             volume: Volume::Linear(0.5),
         },
+        // per track volume for crossfades
+        sample_effects![VolumeNode::default()],
     ));
     cmds.spawn((
         SamplerPool(Sfx),
@@ -75,6 +86,62 @@ fn spawn_pools(mut master: Single<&mut VolumeNode, With<MainBus>>, mut cmds: Com
             volume: Volume::Linear(0.5),
         },
     ));
+    cmds.spawn((
+        SamplerPool(Ambience),
+        VolumeNode {
+            volume: Volume::Linear(0.5),
+        },
+        // attenuated and panned relative to the closest `SpatialListener3D`,
+        // players without a `Transform` are heard everywhere
+        sample_effects![SpatialBasicNode::default()],
+    ));
+    cmds.spawn((
+        SamplerPool(Ui),
+        VolumeNode {
+            volume: Volume::Linear(0.5),
+        },
+    ));
+}
+
+/// Keeps the bus volumes in line with [`Settings::sound`], or silent while muted
+#[allow(clippy::type_complexity)]
+pub fn apply_volumes(
+    settings: Res<Settings>,
+    state: Option<Res<GameState>>,
+    mut master: Query<&mut VolumeNode, With<MainBus>>,
+    mut pools: Query<
+        (
+            &mut VolumeNode,
+            Has<SamplerPool<Music>>,
+            Has<SamplerPool<Ambience>>,
+            Has<SamplerPool<Ui>>,
+        ),
+        (
+            Or<(
+                With<SamplerPool<Music>>,
+                With<SamplerPool<Sfx>>,
+                With<SamplerPool<Ambience>>,
+                With<SamplerPool<Ui>>,
+            )>,
+            Without<MainBus>,
+        ),
+    >,
+) {
+    for mut node in &mut master {
+        node.volume = Volume::Linear(settings.sound.general);
+    }
+    let muted = state.is_some_and(|s| s.muted);
+    for (mut node, music, ambience, ui) in &mut pools {
+        // the general level is already on the main bus
+        let sound = &settings.sound;
+        node.volume = match (music, ambience, ui) {
+            _ if muted => Volume::SILENT,
+            (true, ..) => Volume::Linear(sound.music),
+            (_, true, _) => Volume::Linear(sound.ambience),
+            (.., true) => Volume::Linear(sound.ui),
+            _ => Volume::Linear(sound.sfx),
+        };
+    }
 }
 
 /// An organizational marker component that indicates that [`SamplePlayer`] should be routed to the music sampler pool.
@@ -120,3 +187,20 @@ pub struct Music;
 #[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
 #[reflect(Component)]
 pub struct Sfx;
+
+/// An organizational marker component that indicates that [`SamplePlayer`] should be routed to the ambience sampler pool.
+///
+/// Suitable for background loops (e.g. wind, birds, a river) and the level's
+/// [`AudioEmitter`](crate::models::AudioEmitter)s.
+/// A player with a [`Transform`] is placed in the world, its distance and direction to the
+/// [`SpatialListener3D`] set the volume and panning. One without it is heard the same everywhere.
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub struct Ambience;
+
+/// An organizational marker component that indicates that [`SamplePlayer`] should be routed to the UI sampler pool.
+///
+/// Suitable for menu feedback (e.g. button hover and press).
+#[derive(PoolLabel, Debug, Clone, PartialEq, Eq, Hash, Default, Reflect)]
+#[reflect(Component)]
+pub struct Ui;
@@ -19,6 +19,8 @@ pub fn plugin(app: &mut App) {
 pub fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         SceneCamera,
+        // hears the placed sounds of the `Ambience` pool
+        SpatialListener3D,
         IsDefaultUiCamera,
         Camera3d::default(),
         Camera {
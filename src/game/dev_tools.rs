@@ -1,10 +1,22 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 use super::*;
 use bevy::{
-    dev_tools::states::log_transitions, input::common_conditions::input_toggle_active,
+    dev_tools::states::log_transitions,
+    input::common_conditions::{input_just_pressed, input_toggle_active},
     ui::UiDebugOptions,
 };
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
+#[cfg(feature = "dev_native")]
+use bevy_inspector_egui::{
+    bevy_egui::{EguiContextPass, EguiContexts, egui},
+    reflect_inspector::ui_for_value,
+};
+#[cfg(feature = "dev_native")]
+use std::fs;
+
+/// Written to by the movement tuning panel, the asset watcher then reloads it
+#[cfg(feature = "dev_native")]
+const CONFIG_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/config.ron");
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(EguiPlugin {
@@ -12,9 +24,214 @@ pub(super) fn plugin(app: &mut App) {
     })
     .add_plugins(WorldInspectorPlugin::new().run_if(input_toggle_active(false, KeyCode::Backquote)))
     .add_systems(Update, log_transitions::<Screen>)
+    .add_systems(
+        Update,
+        cycle_weather.run_if(in_state(Screen::Gameplay).and(input_just_pressed(KeyCode::F6))),
+    )
     .add_observer(toggle_debug_ui);
+
+    // writes to the source tree, so only for native dev builds
+    #[cfg(feature = "dev_native")]
+    app.add_systems(
+        EguiContextPass,
+        movement_tuning
+            .run_if(resource_exists::<Config>.and(input_toggle_active(false, KeyCode::F7))),
+    );
 }
 
 fn toggle_debug_ui(_: Trigger<ToggleDebugUi>, mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+/// Forces the next [`WeatherKind`] without waiting for the weather timer
+fn cycle_weather(weather: Res<Weather>, mut commands: Commands) {
+    commands.trigger(ForceWeather(weather.dominant().next()));
+}
+
+/// Tweaks [`Movement`] live, saving writes the `movement` block back to `config.ron`
+#[cfg(feature = "dev_native")]
+fn movement_tuning(
+    mut contexts: EguiContexts,
+    registry: Res<AppTypeRegistry>,
+    mut cfg: ResMut<Config>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    egui::Window::new("Movement tuning").show(ctx, |ui| {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let movement = &mut cfg.bypass_change_detection().player.movement;
+            if ui_for_value(movement, ui, &registry.read()) {
+                cfg.set_changed();
+            }
+        });
+        if ui.button("Save to config.ron").clicked() {
+            match save_movement(&cfg.player.movement) {
+                Ok(()) => info!("saved movement tuning to {CONFIG_PATH}"),
+                Err(e) => error!("failed to save movement tuning: {e}"),
+            }
+        }
+    });
+}
+
+/// Replaces only the `player.movement` block so the rest of the file keeps its comments.
+/// The result is parsed back before writing, a failed splice leaves the file untouched
+#[cfg(feature = "dev_native")]
+fn save_movement(movement: &Movement) -> Result<(), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(CONFIG_PATH)?;
+    fs::write(CONFIG_PATH, splice_movement(&content, movement)?)?;
+    Ok(())
+}
+
+#[cfg(feature = "dev_native")]
+fn splice_movement(
+    content: &str,
+    movement: &Movement,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let (start, end) =
+        value_span(content, &["player", "movement"]).ok_or("no player.movement in config.ron")?;
+
+    // indent the nested lines to where the block sits in the file
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indent: String = content[line_start..start]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    let pretty = ron::ser::PrettyConfig::new()
+        .indentor("    ")
+        .struct_names(false);
+    let block = ron::ser::to_string_pretty(movement, pretty)?.replace('\n', &format!("\n{indent}"));
+
+    let updated = format!("{}{block}{}", &content[..start], &content[end..]);
+    ron::from_str::<Config>(&updated)?;
+    Ok(updated)
+}
+
+/// Byte range of the value of a nested struct field, e.g. `["player", "movement"]`.
+/// Comments, strings and chars are skipped, so only real fields match.
+/// `None` if the field is missing or the brackets don't add up
+#[cfg(feature = "dev_native")]
+fn value_span(content: &str, path: &[&str]) -> Option<(usize, usize)> {
+    let bytes = content.as_bytes();
+    let (mut i, mut depth, mut matched) = (0usize, 0usize, 0);
+    let mut value_start = None;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i += content[i..].find('\n').unwrap_or(bytes.len() - i);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += content[i..]
+                    .find("*/")
+                    .map_or(bytes.len() - i, |end| end + 2);
+                continue;
+            }
+            // raw strings, `r"..."` or `r#"..."#`, can't contain escapes
+            b'r' if matches!(bytes.get(i + 1), Some(b'"' | b'#')) => {
+                let hashes =
+                    content[i + 1..].len() - content[i + 1..].trim_start_matches('#').len();
+                let open = i + 1 + hashes;
+                if bytes.get(open) != Some(&b'"') {
+                    // raw identifier like `r#None`
+                    i = open;
+                    continue;
+                }
+                let close = format!("\"{}", "#".repeat(hashes));
+                i = content[open + 1..]
+                    .find(&close)
+                    .map(|end| open + 1 + end + close.len())?;
+                continue;
+            }
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth = depth.checked_sub(1)?;
+                if let Some(start) = value_start
+                    && depth == matched
+                {
+                    return Some((start, i + 1));
+                }
+            }
+            c if value_start.is_none()
+                && depth == matched + 1
+                && (c.is_ascii_alphabetic() || c == b'_') =>
+            {
+                let end = content[i..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .map_or(bytes.len(), |len| i + len);
+                let rest = &content[end..];
+                let colon = rest.len() - rest.trim_start().len();
+                if &content[i..end] == path[matched] && rest[colon..].starts_with(':') {
+                    matched += 1;
+                    if matched == path.len() {
+                        let value = &content[end + colon + 1..];
+                        let start = end + colon + 1 + value.len() - value.trim_start().len();
+                        value_start = Some(start);
+                        i = start;
+                        continue;
+                    }
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(all(test, feature = "dev_native"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_span_skips_comments_strings_and_chars() {
+        let content = r##"(
+    // player: (movement: (speed: 0)),
+    name: "player: (movement: ())",
+    other: r#"movement: )"#,
+    quote: ')',
+    /* player: ( */
+    player: (
+        movement: (speed: 10, label: ')'), // trailing )
+        health: 100,
+    ),
+    kind: r#None,
+)"##;
+        let (start, end) = value_span(content, &["player", "movement"]).unwrap();
+        assert_eq!(&content[start..end], "(speed: 10, label: ')')");
+    }
+
+    #[test]
+    fn value_span_rejects_unbalanced_brackets() {
+        assert_eq!(
+            value_span(") player: (movement: ())", &["player", "movement"]),
+            None
+        );
+        assert_eq!(
+            value_span("(player: (movement: (", &["player", "movement"]),
+            None
+        );
+    }
+
+    #[test]
+    fn movement_splices_into_the_shipped_config() {
+        let content = include_str!("../../assets/config.ron");
+        let (start, end) = value_span(content, &["player", "movement"]).unwrap();
+        let mut movement: Movement = ron::from_str(&content[start..end]).unwrap();
+        movement.speed += 1.0;
+
+        let updated = splice_movement(content, &movement).unwrap();
+        let cfg: Config = ron::from_str(&updated).unwrap();
+        assert_eq!(cfg.player.movement.speed, movement.speed);
+        // comments outside the block are kept
+        assert!(updated.contains("// seconds"));
+    }
+}
@@ -6,6 +6,8 @@ mod camera;
 #[cfg(any(feature = "dev_native", not(target_arch = "wasm32")))]
 mod dev_tools;
 mod mood;
+#[cfg(not(target_arch = "wasm32"))]
+mod save;
 
 pub fn plugin(app: &mut App) {
     // app.insert_resource(Score(0));
@@ -15,6 +17,8 @@ pub fn plugin(app: &mut App) {
         scene::plugin,
         player::plugin,
         mood::plugin,
+        #[cfg(not(target_arch = "wasm32"))]
+        save::plugin,
         #[cfg(any(feature = "dev_native", not(target_arch = "wasm32")))]
         dev_tools::plugin,
         screens::plugin,
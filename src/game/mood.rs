@@ -1,159 +1,432 @@
-//! An abstraction for changing mood of the game depending on some triggers
+//! An abstraction for changing mood of the game depending on some triggers.
+//! Each mood has a shuffled playlist of [`Piece`]s from the [`Soundtrack`],
+//! the stems of the playing piece follow [`MusicIntensity`].
 use super::*;
-use bevy::ecs::{component::ComponentId, observer::TriggerTargets};
+use bevy_seedling::{pool::PlaybackCompletionEvent, sample::Sample};
 use rand::prelude::*;
-
-const FADE_TIME: f32 = 2.0;
+use std::collections::HashMap;
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(OnExit(Screen::Gameplay), stop_soundtrack)
+    app.init_resource::<MoodZones>()
+        .init_resource::<Playlist>()
+        .add_systems(
+            OnExit(Screen::Gameplay),
+            (stop_soundtrack, reset_zones, reset_intensity),
+        )
         .add_systems(OnEnter(Screen::Gameplay), start_soundtrack)
         .add_systems(
             Update,
-            trigger_mood_change.run_if(in_state(Screen::Gameplay)), // (fade_in, fade_out)
+            (track_zones, trigger_mood_change, update_intensity)
+                .chain()
+                .in_set(AppSystems::Update)
+                .run_if(in_state(Screen::Gameplay)),
         )
-        // .add_observer(trigger_mood_change)
-        .add_observer(change_mood);
+        .add_systems(
+            Update,
+            (start_next_piece, fade_in, fade_out, apply_stem_volumes)
+                .chain()
+                .in_set(AppSystems::Update)
+                .run_if(resource_exists::<Soundtrack>),
+        )
+        .add_observer(change_mood)
+        .add_observer(advance_playlist);
+}
+
+/// Stem of the playing piece, its volume is `fade` times the stem gain at the current intensity
+#[derive(Component)]
+struct MusicLayer {
+    stem: Stem,
+    fade: f32,
+}
+
+/// Shuffled order of the pieces per mood and the piece to start next
+#[derive(Resource, Default)]
+struct Playlist {
+    /// Pieces left in the current shuffle of each mood, taken from the back
+    queues: HashMap<MoodType, Vec<usize>>,
+    /// Last piece played per mood, a new shuffle never starts with it
+    last: HashMap<MoodType, usize>,
+    /// Piece waiting for the gap to pass and its stems to load, so they all start on the same frame
+    next: Option<(MoodType, usize, Timer)>,
+}
+
+impl Playlist {
+    fn queue(&mut self, mood: &MoodType, soundtrack: &Soundtrack, gap: f32) {
+        let count = soundtrack.pieces(mood).len();
+        if count == 0 {
+            warn!("no music for mood {mood:?}");
+            self.next = None;
+            return;
+        }
+
+        let last = self.last.get(mood).copied();
+        let queue = self.queues.entry(mood.clone()).or_default();
+        // the soundtrack may have shrunk on hot reload
+        queue.retain(|i| *i < count);
+        if queue.is_empty() {
+            *queue = (0..count).collect();
+            queue.shuffle(&mut thread_rng());
+            if count > 1 && queue.last().copied() == last {
+                queue.swap(0, count - 1);
+            }
+        }
+        let Some(index) = queue.pop() else {
+            return;
+        };
+        self.last.insert(mood.clone(), index);
+        self.next = Some((
+            mood.clone(),
+            index,
+            Timer::from_seconds(gap, TimerMode::Once),
+        ));
+    }
 }
 
-// TODO: implement different music states
-// TODO: basic track/mood change per zone
-// good structure in this example: <https://github.com/bevyengine/bevy/blob/main/examples/audio/soundtrack.rs#L29>
 fn start_soundtrack(
-    mut cmds: Commands,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    // boombox: Query<Entity, With<Boombox>>,
+    state: Res<GameState>,
+    soundtrack: Option<Res<Soundtrack>>,
+    mut playlist: ResMut<Playlist>,
 ) {
-    let mut rng = thread_rng();
-    let handle = sources.explore.choose(&mut rng).unwrap();
-
-    // // Play music from boombox entity
-    // cmds
-    //     .entity(boombox.single()?)
-    //     .insert(music(handle.clone(), settings.music());
-    // Or just play music
-    cmds.spawn((
-        Music,
-        SamplePlayer::new(handle.clone())
-            .with_volume(settings.music())
-            .looping(),
-    ));
+    if let Some(soundtrack) = soundtrack {
+        playlist.queue(&state.current_mood, &soundtrack, 0.0);
+    }
 }
 
 fn stop_soundtrack(
-    // boombox: Query<Entity, With<Boombox>>,
-    mut bg_music: Query<&mut PlaybackSettings, With<Music>>,
+    mut commands: Commands,
+    mut playlist: ResMut<Playlist>,
+    bg_music: Query<Entity, (With<Music>, With<MusicLayer>)>,
 ) {
-    for mut s in bg_music.iter_mut() {
-        info!("pause track:{s:?}");
-        s.pause();
+    playlist.next = None;
+    for track in bg_music.iter() {
+        info!("fade out track:{track}");
+        commands.entity(track).remove::<FadeIn>().insert(FadeOut);
     }
 }
 
-// fn check_sensor_collision(mut collision_query: Query<(&CollidingEntities, &Sensor), With<Player>>) {
-//     for (colliding_entities, sensor) in collision_query.iter() {
-//         if !colliding_entities.is_empty() {
-//             println!("hello");
-//         }
-//     }
-// }
-fn trigger_mood_change(
+/// Once a stem of the playing piece ends, the rest of it fades out and the next piece is
+/// queued after [`Timers::music_gap`]
+fn advance_playlist(
+    on: Trigger<PlaybackCompletionEvent>,
+    cfg: Res<Config>,
+    state: Res<GameState>,
+    soundtrack: Option<Res<Soundtrack>>,
+    mut playlist: ResMut<Playlist>,
+    layers: Query<Entity, (With<MusicLayer>, Without<FadeOut>)>,
     mut commands: Commands,
-    mut zones: Query<Entity, With<Zone>>,
-    mut player: Query<Entity, With<Player>>,
-    collisions: Collisions,
 ) {
-    let Ok(player) = player.single_mut() else {
+    if !layers.contains(on.target()) || playlist.next.is_some() {
+        return;
+    }
+    let Some(soundtrack) = soundtrack else {
         return;
     };
-    for zone in zones.iter() {
-        if collisions.contains(player, zone) {
-            let ids: Vec<ComponentId> = zone.components().collect();
-            info!("components:{ids:?}");
-            info!("sensors: player:{player}, zone:{zone}");
+    for layer in &layers {
+        if layer != on.target() {
+            commands.entity(layer).remove::<FadeIn>().insert(FadeOut);
         }
     }
+    playlist.queue(&state.current_mood, &soundtrack, cfg.timers.music_gap);
+}
+
+fn start_next_piece(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    server: Res<AssetServer>,
+    soundtrack: Res<Soundtrack>,
+    samples: Res<StemSamples>,
+    mut playlist: ResMut<Playlist>,
+    mut commands: Commands,
+) {
+    let Some((mood, index, gap)) = &mut playlist.next else {
+        return;
+    };
+    if !gap.tick(time.delta()).finished() {
+        return;
+    }
+    let Some(piece) = soundtrack.pieces(mood).get(*index) else {
+        playlist.next = None;
+        return;
+    };
+    let Some(stems) = samples.piece(piece, &server) else {
+        return;
+    };
+    if stems.is_empty() {
+        warn!("no stem of {} could be loaded, skipping it", piece.name);
+        let mood = mood.clone();
+        playlist.queue(&mood, &soundtrack, cfg.timers.music_gap);
+        return;
+    }
+
+    info!("playing {} for {mood:?}", piece.name);
+    for (stem, handle) in stems {
+        commands.spawn(layer(handle, stem.clone()));
+    }
+    commands.trigger(NowPlaying {
+        mood: mood.clone(),
+        name: piece.name.clone(),
+    });
+    playlist.next = None;
+}
+
+/// Music stem that starts silent and is faded in by [`fade_in`], despawned once it ends
+fn layer(handle: Handle<Sample>, stem: Stem) -> impl Bundle {
+    (
+        Name::new(format!("Music Stem: {}", stem.sample)),
+        Music,
+        SamplePlayer::new(handle),
+        sample_effects![VolumeNode {
+            volume: Volume::SILENT
+        }],
+        MusicLayer { stem, fade: 0.0 },
+        FadeIn,
+    )
+}
+
+/// Zones the player is inside, in the order they were entered
+#[derive(Resource, Default)]
+struct MoodZones {
+    /// One entry per zone collider touched, so zones made of several colliders
+    /// are only left once the player is out of all of them
+    inside: Vec<Entity>,
+    /// Mood to restore once the player left every zone
+    outside: Option<MoodType>,
+    /// Mood waiting for [`Timers::mood_change`] to pass, so walking along a zone border
+    /// doesn't flip the music back and forth
+    pending: Option<(MoodType, Timer)>,
+}
+
+fn reset_zones(mut zones: ResMut<MoodZones>) {
+    *zones = MoodZones::default();
+}
+
+fn track_zones(
+    mut started: EventReader<CollisionStarted>,
+    mut ended: EventReader<CollisionEnded>,
+    mut zones: ResMut<MoodZones>,
+    state: Res<GameState>,
+    player: Query<Entity, With<Player>>,
+    colliders: Query<&ColliderOf>,
+    parents: Query<&ChildOf>,
+    zone_query: Query<(), With<Zone>>,
+    mut commands: Commands,
+) {
+    let Ok(player) = player.single() else {
+        return;
+    };
+    // the player may be a child collider of its body, a `Zone` tag may sit on the collider,
+    // its body or any parent of them, e.g. a skein empty with collider children
+    let resolve = |e: Entity| colliders.get(e).map(|c| c.body).unwrap_or(e);
+    let zone_of = |e: Entity| {
+        [e, resolve(e)]
+            .into_iter()
+            .flat_map(|e| std::iter::once(e).chain(parents.iter_ancestors(e)))
+            .find(|e| zone_query.contains(*e))
+    };
+    let find_zone = |a: Entity, b: Entity| {
+        if resolve(a) == player {
+            zone_of(b)
+        } else if resolve(b) == player {
+            zone_of(a)
+        } else {
+            None
+        }
+    };
+
+    for CollisionStarted(a, b) in started.read() {
+        let Some(zone) = find_zone(*a, *b) else {
+            continue;
+        };
+        if zones.inside.is_empty() && zones.outside.is_none() {
+            zones.outside = Some(state.current_mood.clone());
+        }
+        if !zones.inside.contains(&zone) {
+            info!("entered zone {zone}");
+            commands.trigger(Autosave);
+        }
+        zones.inside.push(zone);
+    }
+
+    for CollisionEnded(a, b) in ended.read() {
+        let Some(zone) = find_zone(*a, *b) else {
+            continue;
+        };
+        if let Some(i) = zones.inside.iter().position(|z| *z == zone) {
+            zones.inside.remove(i);
+        }
+        if !zones.inside.contains(&zone) {
+            info!("left zone {zone}");
+        }
+    }
+}
+
+fn trigger_mood_change(
+    time: Res<Time>,
+    cfg: Res<Config>,
+    state: Res<GameState>,
+    mut zones: ResMut<MoodZones>,
+    zone_query: Query<(Has<Combat>, Option<&ZonePriority>), With<Zone>>,
+    mut commands: Commands,
+) {
+    // latest entered zone wins a tie, `max_by_key` returns the last max
+    let target = zones
+        .inside
+        .iter()
+        .filter_map(|z| zone_query.get(*z).ok())
+        .map(|(combat, priority)| {
+            let mood = if combat {
+                MoodType::Combat
+            } else {
+                MoodType::Exploration
+            };
+            let priority = priority.map(|p| p.0).unwrap_or(combat as i32);
+            (mood, priority)
+        })
+        .max_by_key(|(_, priority)| *priority)
+        .map(|(mood, _)| mood)
+        .or_else(|| zones.outside.clone())
+        .unwrap_or_else(|| state.current_mood.clone());
+
+    if target == state.current_mood {
+        zones.pending = None;
+        if zones.inside.is_empty() {
+            zones.outside = None;
+        }
+        return;
+    }
+
+    if zones
+        .pending
+        .as_ref()
+        .is_none_or(|(mood, _)| *mood != target)
+    {
+        let timer = Timer::from_seconds(cfg.timers.mood_change, TimerMode::Once);
+        zones.pending = Some((target, timer));
+    }
+    let Some((mood, timer)) = zones.pending.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).finished() {
+        info!("mood change: {:?} -> {mood:?}", state.current_mood);
+        commands.trigger(ChangeMood(mood.clone()));
+        zones.pending = None;
+    }
 }
 
 // Every time the GameState resource changes, this system is run to trigger the song change.
 fn change_mood(
     on: Trigger<ChangeMood>,
-    settings: Res<Settings>,
-    sources: Res<AudioSources>,
+    soundtrack: Option<Res<Soundtrack>>,
     mut state: ResMut<GameState>,
-    mut collisions: Query<(&Zone, &Sensor), With<Player>>,
-    music: Query<Entity, With<SamplerPool<Music>>>,
+    mut playlist: ResMut<Playlist>,
+    music: Query<Entity, (With<Music>, With<MusicLayer>)>,
     mut commands: Commands,
 ) {
     let mood = &on.0;
-    let mut rng = thread_rng();
 
     // Fade out all currently running tracks
     for track in music.iter() {
-        commands.entity(track).insert(FadeOut);
-    }
-
-    // Spawn a new music with the appropriate soundtrack based on new mood
-    // Volume is set to start at zero and is then increased by the fade_in system.
-    match mood {
-        MoodType::Exploration => {
-            let handle = sources.explore.choose(&mut rng).unwrap();
-            commands.spawn((
-                Music,
-                SamplePlayer::new(handle.clone())
-                    .with_volume(settings.music())
-                    .looping(),
-                FadeIn,
-            ));
-        }
-        MoodType::Combat => {
-            let handle = sources.combat.choose(&mut rng).unwrap();
-            commands.spawn((
-                Music,
-                SamplePlayer::new(handle.clone())
-                    .with_volume(settings.music())
-                    .looping(),
-                FadeIn,
-            ));
-        }
+        commands.entity(track).remove::<FadeIn>().insert(FadeOut);
+    }
+
+    // The next piece fades in from silence once its stems are loaded
+    if let Some(soundtrack) = soundtrack {
+        playlist.queue(mood, &soundtrack, 0.0);
     }
     state.current_mood = mood.clone();
 }
 
-// Fades in the audio of entities that has the FadeIn component. Removes the FadeIn component once
+fn reset_intensity(mut intensity: ResMut<MusicIntensity>) {
+    *intensity = MusicIntensity::default();
+}
+
+/// Sums up what makes the music intense right now and eases towards it
+fn update_intensity(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    state: Res<GameState>,
+    mut intensity: ResMut<MusicIntensity>,
+    player: Query<(&Locomotion, &GlobalTransform), With<Player>>,
+    enemies: Query<&GlobalTransform, (With<Health>, Without<Player>, Without<Dead>)>,
+) {
+    let music = &cfg.music;
+    let mut target = 0.0;
+    if state.current_mood == MoodType::Combat {
+        target += music.combat;
+    }
+    if let Ok((locomotion, tf)) = player.single() {
+        let near = enemies
+            .iter()
+            .filter(|e| e.translation().distance(tf.translation()) <= music.enemy_radius)
+            .count();
+        target += near as f32 * music.enemy;
+        if *locomotion == Locomotion::Sprint {
+            target += music.sprint;
+        }
+    }
+    intensity.target = target.clamp(0.0, 1.0);
+
+    let step = music.intensity_rate * time.delta_secs();
+    let delta = (intensity.target - intensity.current).clamp(-step, step);
+    intensity.current += delta;
+}
+
+/// Crossfade step for this frame. The [`Settings::music`] level and mute live on the
+/// [`Music`] pool node, so the per-track node only goes between silence and unity.
+/// While muted there is nothing to hear, so fades finish at once.
+fn fade_step(time: &Time, cfg: &Config, state: &GameState) -> f32 {
+    if state.muted || cfg.timers.music_fade <= 0.0 {
+        1.0
+    } else {
+        time.delta_secs() / cfg.timers.music_fade
+    }
+}
+
+// Fades in the stems that have the FadeIn component. Removes the FadeIn component once
 // full volume is reached.
-// fn fade_in(
-//     time: Res<Time>,
-//     mut commands: Commands,
-//     mut music: Query<(&mut VolumeNode, Entity), With<FadeIn>>,
-// ) {
-//     for (mut audio, entity) in music.iter_mut() {
-//         let current_volume = audio.volume;
-//         audio.set_volume(
-//             current_volume.fade_towards(Volume::Linear(1.0), time.delta_secs() / FADE_TIME),
-//         );
-//         if audio.volume().to_linear() >= 1.0 {
-//             audio.set_volume(Volume::Linear(1.0));
-//             commands.entity(entity).remove::<FadeIn>();
-//         }
-//     }
-// }
-
-// Fades out the audio of entities that has the FadeOut component. Despawns the entities once audio
+fn fade_in(
+    time: Res<Time>,
+    cfg: Res<Config>,
+    state: Res<GameState>,
+    mut commands: Commands,
+    mut music: Query<(Entity, &mut MusicLayer), With<FadeIn>>,
+) {
+    let step = fade_step(&time, &cfg, &state);
+    for (entity, mut layer) in music.iter_mut() {
+        layer.fade = (layer.fade + step).min(1.0);
+        if layer.fade >= 1.0 {
+            commands.entity(entity).remove::<FadeIn>();
+        }
+    }
+}
+
+// Fades out the stems that have the FadeOut component. Despawns the entities once audio
 // volume reaches zero.
-// fn fade_out(
-//     time: Res<Time>,
-//     mut commands: Commands,
-//     mut music: Query<(&mut VolumeNode, Entity), With<FadeOut>>,
-// ) {
-//     for (mut audio, entity) in music.iter_mut() {
-//         let current_volume = audio.volume;
-//         audio.set_volume(
-//             current_volume.fade_towards(Volume::Linear(0.0), time.delta_secs() / FADE_TIME),
-//         );
-//         if audio.volume().to_linear() <= 0.0 {
-//             commands.entity(entity).despawn();
-//         }
-//     }
-// }
+fn fade_out(
+    time: Res<Time>,
+    cfg: Res<Config>,
+    state: Res<GameState>,
+    mut commands: Commands,
+    mut music: Query<(Entity, &mut MusicLayer), With<FadeOut>>,
+) {
+    let step = fade_step(&time, &cfg, &state);
+    for (entity, mut layer) in music.iter_mut() {
+        layer.fade = (layer.fade - step).max(0.0);
+        if layer.fade <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn apply_stem_volumes(
+    intensity: Res<MusicIntensity>,
+    music: Query<(&MusicLayer, &SampleEffects)>,
+    mut volumes: Query<&mut VolumeNode>,
+) {
+    for (layer, effects) in music.iter() {
+        let Ok(mut node) = volumes.get_effect_mut(effects) else {
+            continue;
+        };
+        node.volume = Volume::Linear(layer.fade * layer.stem.gain(intensity.current));
+    }
+}
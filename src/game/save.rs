@@ -0,0 +1,269 @@
+//! Writes and restores [`SaveData`]: player transform and state, mood, sun angle and every
+//! component marked with [`ReflectSave`]
+use super::*;
+use bevy::{
+    reflect::{
+        TypeRegistry,
+        serde::{ReflectDeserializer, ReflectSerializer},
+    },
+    tasks::IoTaskPool,
+};
+use serde::de::DeserializeSeed;
+use std::collections::{HashMap, HashSet};
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), start_autosave_timer)
+        // StateScoped entities are despawned with commands, so the player is still there
+        .add_systems(OnExit(Screen::Gameplay), trigger_autosave)
+        .add_systems(
+            Update,
+            (
+                tick_autosave
+                    .in_set(AppSystems::TickTimers)
+                    .run_if(resource_exists::<AutosaveTimer>),
+                apply_save
+                    .run_if(resource_exists::<PendingLoad>)
+                    .in_set(AppSystems::Update),
+            )
+                .run_if(in_state(Screen::Gameplay)),
+        )
+        .add_observer(save_game)
+        .add_observer(load_game)
+        .add_observer(autosave);
+}
+
+/// Save read from disk, applied once the player is spawned
+#[derive(Resource)]
+struct PendingLoad(SaveData);
+
+#[derive(Resource, Deref, DerefMut)]
+struct AutosaveTimer(Timer);
+
+fn save_game(on: Trigger<SaveGame>, screen: Res<State<Screen>>, mut commands: Commands) {
+    if *screen.get() != Screen::Gameplay {
+        warn!("nothing to save outside of gameplay");
+        return;
+    }
+
+    let slot = on.0.clone();
+    commands.queue(move |world: &mut World| {
+        match collect_save(world).and_then(|data| data.save(&slot)) {
+            Ok(()) => info!("saved game to '{}'", SaveData::path(&slot).display()),
+            Err(e) => error!("unable to save game to slot '{slot}': {e}"),
+        }
+    });
+}
+
+fn start_autosave_timer(cfg: Res<Config>, mut commands: Commands) {
+    if cfg.timers.autosave > 0.0 {
+        let timer = Timer::from_seconds(cfg.timers.autosave, TimerMode::Repeating);
+        commands.insert_resource(AutosaveTimer(timer));
+    } else {
+        commands.remove_resource::<AutosaveTimer>();
+    }
+}
+
+fn tick_autosave(time: Res<Time>, mut timer: ResMut<AutosaveTimer>, mut commands: Commands) {
+    if timer.tick(time.delta()).just_finished() {
+        commands.trigger(Autosave);
+    }
+}
+
+fn trigger_autosave(mut commands: Commands) {
+    commands.trigger(Autosave);
+}
+
+/// Collects and serializes on the main thread, the file rotation and write run on the IO pool.
+/// A save that fails to serialize is dropped, so the previous autosave stays untouched.
+fn autosave(_: Trigger<Autosave>, mut commands: Commands) {
+    commands.queue(|world: &mut World| {
+        let content = match collect_save(world).and_then(|data| data.to_ron()) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("autosave skipped: {e}");
+                return;
+            }
+        };
+        IoTaskPool::get()
+            .spawn(async move {
+                match SaveData::write(AUTOSAVE_SLOT, &content, AUTOSAVE_BACKUPS) {
+                    Ok(()) => info!("autosaved to '{}'", SaveData::path(AUTOSAVE_SLOT).display()),
+                    Err(e) => error!("unable to write autosave: {e}"),
+                }
+            })
+            .detach();
+    });
+}
+
+fn load_game(
+    on: Trigger<LoadGame>,
+    screen: Res<State<Screen>>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut commands: Commands,
+) {
+    let slot = &on.0;
+    let data = match SaveData::read(slot) {
+        Ok(data) => data,
+        Err(e) => {
+            error!("unable to load slot '{slot}': {e}");
+            return;
+        }
+    };
+
+    info!("loading slot '{slot}'");
+    commands.insert_resource(PendingLoad(data));
+    if *screen.get() == Screen::Gameplay {
+        return;
+    }
+    if resource_handles.is_all_done() {
+        next_screen.set(Screen::Gameplay);
+    } else {
+        next_screen.set(Screen::Loading);
+    }
+}
+
+fn collect_save(world: &mut World) -> Result<SaveData, SaveError> {
+    let mut named = world.query::<(Entity, &Name)>();
+    let (player_entity, tf, player) = world
+        .query::<(Entity, &Transform, &Player)>()
+        .single(world)
+        .map_err(|_| SaveError::Missing("player"))?;
+    let state = world.resource::<GameState>();
+    let time = world.resource::<TimeOfDay>();
+    let registry = world.resource::<AppTypeRegistry>().read();
+
+    let player_save = PlayerSave {
+        translation: tf.translation.to_array(),
+        rotation: tf.rotation.to_array(),
+        speed: player.speed,
+        components: saved_components(world, &registry, player_entity)?,
+    };
+    let mut data = SaveData::new(player_save, state.current_mood.clone(), time.sun_angle());
+
+    // entities are matched by name on load, so a name shared by several can't be restored
+    let mut duplicates = HashSet::new();
+    for (entity, name) in named.iter(world) {
+        if entity == player_entity {
+            continue;
+        }
+        let components = saved_components(world, &registry, entity)?;
+        let name = name.to_string();
+        if components.is_empty() || duplicates.contains(&name) {
+            continue;
+        }
+        if data.entities.remove(&name).is_some() {
+            warn!("several saved entities are named '{name}', skipping them");
+            duplicates.insert(name);
+            continue;
+        }
+        data.entities.insert(name, components);
+    }
+
+    Ok(data)
+}
+
+/// Components of `entity` marked with [`ReflectSave`], serialized with reflection
+fn saved_components(
+    world: &World,
+    registry: &TypeRegistry,
+    entity: Entity,
+) -> Result<Vec<String>, SaveError> {
+    let entity = world.entity(entity);
+    let mut components = vec![];
+    for (registration, _) in registry.iter_with_data::<ReflectSave>() {
+        let Some(component) = registration
+            .data::<ReflectComponent>()
+            .and_then(|c| c.reflect(entity))
+        else {
+            continue;
+        };
+        let serializer = ReflectSerializer::new(component.as_partial_reflect(), registry);
+        components.push(ron::to_string(&serializer)?);
+    }
+    Ok(components)
+}
+
+fn apply_save(world: &mut World) {
+    let Some(PendingLoad(data)) = world.remove_resource::<PendingLoad>() else {
+        return;
+    };
+    let Ok(player) = world.query_filtered::<Entity, With<Player>>().single(world) else {
+        error!("no player to apply the save to");
+        return;
+    };
+
+    {
+        let mut entity = world.entity_mut(player);
+        if let Some(mut tf) = entity.get_mut::<Transform>() {
+            tf.translation = Vec3::from_array(data.player.translation);
+            tf.rotation = Quat::from_array(data.player.rotation);
+        }
+        if let Some(mut velocity) = entity.get_mut::<LinearVelocity>() {
+            velocity.0 = Vec3::ZERO;
+        }
+        if let Some(mut p) = entity.get_mut::<Player>() {
+            p.speed = data.player.speed;
+        }
+        // modifiers are input state, a loaded game starts walking and the speed follows
+        if let Some(mut locomotion) = entity.get_mut::<Locomotion>() {
+            *locomotion = Locomotion::Walk;
+        }
+    }
+    insert_components(world, player, &data.player.components);
+
+    let mut named = world.query::<(Entity, &Name)>();
+    let mut targets: HashMap<&String, Vec<Entity>> = HashMap::new();
+    for (entity, name) in named.iter(world) {
+        if let Some((name, _)) = data.entities.get_key_value(name.as_str()) {
+            targets.entry(name).or_default().push(entity);
+        }
+    }
+    for (name, entities) in targets {
+        let [entity] = entities[..] else {
+            warn!("several entities are named '{name}', skipping its saved components");
+            continue;
+        };
+        insert_components(world, entity, &data.entities[name]);
+    }
+
+    world.insert_resource(TimeOfDay::from_sun_angle(data.sun_angle));
+    let state = world.resource::<GameState>();
+    if state.current_mood != data.mood {
+        world.trigger(ChangeMood(data.mood));
+    }
+    info!("save applied");
+}
+
+fn insert_components(world: &mut World, entity: Entity, components: &[String]) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for component in components {
+        let reflected = match ron::Deserializer::from_str(component) {
+            Ok(mut de) => ReflectDeserializer::new(&registry)
+                .deserialize(&mut de)
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let reflected = match reflected {
+            Ok(reflected) => reflected,
+            Err(e) => {
+                warn!("skipping saved component '{component}': {e}");
+                continue;
+            }
+        };
+        let Some(reflect_component) = reflected
+            .get_represented_type_info()
+            .and_then(|info| registry.get(info.type_id()))
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            warn!("skipping unregistered saved component '{component}'");
+            continue;
+        };
+        reflect_component.insert(
+            &mut world.entity_mut(entity),
+            reflected.as_partial_reflect(),
+            &registry,
+        );
+    }
+}
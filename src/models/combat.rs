@@ -0,0 +1,76 @@
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    // register for skein
+    app.register_type::<Health>()
+        .register_type::<Damage>()
+        .register_type::<MeleeAttack>()
+        .register_type::<MeleeHitbox>();
+}
+
+/// Hit points of anything that can be hurt. Requires [`Damageable`] so melee hitboxes can find it
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), reflect(Save))]
+#[require(Damageable)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Health {
+    pub fn new(max: f32) -> Self {
+        Self { current: max, max }
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(100.0)
+    }
+}
+
+/// Contact damage dealt to any [`Health`] entity touching this collider, e.g. spikes or lava
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, Deref)]
+#[reflect(Component, Default)]
+pub struct Damage(pub f32);
+
+/// Frame data of a single melee swing: telegraph, hit window and cooldown
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttackPhase {
+    #[default]
+    Windup,
+    Active,
+    Recovery,
+}
+
+/// Present on an entity for the whole duration of an attack.
+/// Removed once the [`AttackPhase::Recovery`] timer runs out
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct MeleeAttack {
+    pub phase: AttackPhase,
+    pub timer: Timer,
+    /// Sensor spawned for the [`AttackPhase::Active`] window
+    pub hitbox: Option<Entity>,
+    /// Entities already hit by this swing, so each one takes damage only once
+    pub hits: Vec<Entity>,
+}
+
+impl MeleeAttack {
+    pub fn new(windup: f32) -> Self {
+        Self {
+            phase: AttackPhase::Windup,
+            timer: Timer::from_seconds(windup, TimerMode::Once),
+            hitbox: None,
+            hits: vec![],
+        }
+    }
+}
+
+/// Sensor collider in front of the attacker, alive only during [`AttackPhase::Active`]
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct MeleeHitbox {
+    pub owner: Entity,
+}
@@ -0,0 +1,143 @@
+//! Window mode, resolution, UI scale and frame cap from [`Settings::display`],
+//! applied to the [`PrimaryWindow`] whenever they change
+use super::*;
+use bevy::window::{
+    Monitor, MonitorSelection, PresentMode, PrimaryMonitor, PrimaryWindow, VideoModeSelection,
+    WindowMode,
+};
+use std::fmt::{self, Display, Formatter};
+
+/// Frame caps the Video tab cycles through, `None` leaves the frame rate alone
+pub const FRAME_CAPS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Update, apply_display.run_if(resource_changed::<Settings>));
+    // browsers pace frames themselves, and there is no sleeping on the main thread anyway
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_systems(Last, limit_frame_rate);
+}
+
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+    /// Physical window size, or the fullscreen video mode size.
+    /// `None` keeps the current window size or monitor mode.
+    pub resolution: Option<UVec2>,
+    pub vsync: bool,
+    /// Scale of the whole UI, see [`UiScale`]
+    pub ui_scale: f32,
+    /// Frames per second limit, see [`FRAME_CAPS`]
+    pub frame_cap: Option<u32>,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            mode: DisplayMode::Windowed,
+            resolution: None,
+            vsync: true,
+            ui_scale: 1.0,
+            frame_cap: None,
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Windowed => Self::Borderless,
+            Self::Borderless => Self::Fullscreen,
+            Self::Fullscreen => Self::Windowed,
+        }
+    }
+}
+
+impl Display for DisplayMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Distinct sizes of the monitor's video modes, largest first
+pub fn resolutions(monitor: &Monitor) -> Vec<UVec2> {
+    let mut sizes: Vec<UVec2> = monitor
+        .video_modes
+        .iter()
+        .map(|m| m.physical_size)
+        .collect();
+    sizes.sort_by_key(|s| std::cmp::Reverse((s.x, s.y)));
+    sizes.dedup();
+    sizes
+}
+
+fn apply_display(
+    settings: Res<Settings>,
+    mut applied: Local<Option<DisplaySettings>>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+    monitor: Query<&Monitor, With<PrimaryMonitor>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let wanted = &settings.display;
+    if applied.as_ref() == Some(wanted) {
+        return;
+    }
+    let Ok(mut window) = window.single_mut() else {
+        return;
+    };
+
+    window.mode = match wanted.mode {
+        DisplayMode::Windowed => WindowMode::Windowed,
+        DisplayMode::Borderless => WindowMode::BorderlessFullscreen(MonitorSelection::Current),
+        DisplayMode::Fullscreen => {
+            // highest refresh rate of the chosen size
+            let video_mode = wanted.resolution.and_then(|size| {
+                monitor
+                    .single()
+                    .ok()?
+                    .video_modes
+                    .iter()
+                    .filter(|m| m.physical_size == size)
+                    .max_by_key(|m| m.refresh_rate_millihertz)
+                    .cloned()
+            });
+            let selection =
+                video_mode.map_or(VideoModeSelection::Current, VideoModeSelection::Specific);
+            WindowMode::Fullscreen(MonitorSelection::Current, selection)
+        }
+    };
+    if let (DisplayMode::Windowed, Some(size)) = (wanted.mode, wanted.resolution) {
+        window.resolution.set_physical_resolution(size.x, size.y);
+    }
+    window.present_mode = if wanted.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    ui_scale.0 = wanted.ui_scale;
+
+    info!("applied wanted settings: {:?}", wanted);
+    *applied = Some(wanted.clone());
+}
+
+/// Sleeps away what is left of the frame budget
+#[cfg(not(target_arch = "wasm32"))]
+fn limit_frame_rate(settings: Res<Settings>, mut frame_start: Local<Option<std::time::Instant>>) {
+    use std::time::{Duration, Instant};
+
+    if let (Some(fps), Some(start)) = (settings.display.frame_cap, *frame_start) {
+        let budget = Duration::from_secs_f64(1.0 / fps.max(1) as f64);
+        if let Some(left) = budget.checked_sub(start.elapsed()) {
+            std::thread::sleep(left);
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
@@ -0,0 +1,27 @@
+//! Sound sources placed in the scene, attached to empties in Blender through skein
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<AudioEmitter>();
+}
+
+/// Plays a sample from its position through the ambience pool, looping by default
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct AudioEmitter {
+    /// Asset path, e.g. `audio/sfx/waterfall.ogg`
+    pub sample: String,
+    /// Multiplies the ambience volume
+    pub volume: f32,
+    pub looping: bool,
+}
+
+impl Default for AudioEmitter {
+    fn default() -> Self {
+        Self {
+            sample: String::new(),
+            volume: 1.0,
+            looping: true,
+        }
+    }
+}
@@ -5,21 +5,32 @@ pub fn plugin(app: &mut App) {
         .add_event::<GoTo>()
         .add_event::<OnPress>()
         .add_event::<ChangeMood>()
+        .add_event::<NowPlaying>()
+        .add_event::<Hit>()
+        .add_event::<DamageEvent>()
+        .add_event::<Died>()
+        .add_event::<Autosave>()
         .add_event::<SettingsChanged>()
+        .add_event::<DayPhaseChanged>()
+        .add_event::<ForceWeather>()
         .add_event::<SwitchTab>()
         .add_event::<NewModal>()
         .add_event::<PopModal>()
         .add_event::<ClearModals>()
         .add_event::<FovIncrement>()
         .add_event::<CamCursorToggle>()
-        .add_event::<ToggleVsync>()
         .add_event::<ToggleMute>()
         .add_event::<TogglePause>()
         .add_event::<ToggleDebugUi>()
         .add_event::<ToggleDiagnostics>()
         .add_observer(pause)
         .add_observer(mute)
-        .add_observer(back);
+        .add_observer(back)
+        .add_observer(tab_left)
+        .add_observer(tab_right);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_event::<SaveGame>().add_event::<LoadGame>();
 }
 
 #[derive(Event)]
@@ -39,8 +50,6 @@ pub struct CamCursorToggle;
 #[derive(Event)]
 pub struct FovIncrement;
 #[derive(Event)]
-pub struct ToggleVsync;
-#[derive(Event)]
 pub struct TogglePause;
 #[derive(Event)]
 pub struct ToggleMute;
@@ -50,12 +59,53 @@ pub struct ToggleDiagnostics;
 pub struct ToggleDebugUi;
 #[derive(Event)]
 pub struct ChangeMood(pub MoodType);
+/// Triggered when a [`Piece`] of the soundtrack starts
+#[derive(Event, Debug, Clone)]
+pub struct NowPlaying {
+    pub mood: MoodType,
+    pub name: String,
+}
+/// Triggered on a [`Damageable`] entity when a melee hitbox overlaps it
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Hit {
+    pub attacker: Entity,
+    pub damage: f32,
+}
+/// Triggered on an entity with [`Health`], `source` is used for the knockback direction
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub source: Option<Entity>,
+    pub amount: f32,
+}
+/// Triggered on the player when its [`Health`] drops to zero
+#[derive(Event)]
+pub struct Died;
+/// Writes the current game into the named slot
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Event)]
+pub struct SaveGame(pub String);
+/// Reads the named slot and enters gameplay with it
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Event)]
+pub struct LoadGame(pub String);
+/// Writes the current game into [`AUTOSAVE_SLOT`] in the background, a no-op in the browser
+#[derive(Event)]
+pub struct Autosave;
 /// Event triggered on a UI entity when the [`Interaction`] component on the same entity changes to
 /// [`Interaction::Pressed`]. Observe this event to detect e.g. button presses.
 #[derive(Event)]
 pub struct OnPress;
 #[derive(Event)]
 pub struct SettingsChanged;
+/// Triggered when [`TimeOfDay`] enters a new [`DayPhase`]
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DayPhaseChanged(pub DayPhase);
+/// Fades into the given weather right away, e.g. from the dev tools
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ForceWeather(pub WeatherKind);
+/// Triggered on a [`Slider`] with its new value whenever it is dragged or stepped
+#[derive(Event)]
+pub struct SliderChanged(pub f32);
 
 // ================== trigger events on input ========================
 fn back(
@@ -78,3 +128,9 @@ fn pause(_: Trigger<Started<Pause>>, mut commands: Commands) {
 fn mute(_: Trigger<Started<Mute>>, mut commands: Commands) {
     commands.trigger(ToggleMute);
 }
+fn tab_left(_: Trigger<Started<LeftTab>>, tab: Res<ActiveTab>, mut commands: Commands) {
+    commands.trigger(SwitchTab(tab.0.cycle(-1)));
+}
+fn tab_right(_: Trigger<Started<RightTab>>, tab: Res<ActiveTab>, mut commands: Commands) {
+    commands.trigger(SwitchTab(tab.0.cycle(1)));
+}
@@ -0,0 +1,192 @@
+use super::*;
+use bevy::pbr::AtmosphereSettings;
+use std::fmt::{self, Display, Formatter};
+
+/// Rendering quality, picked from a [`GraphicsPreset`] or tuned option by option.
+#[derive(Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Graphics {
+    /// [`GraphicsPreset::Custom`] as soon as any option differs from the preset
+    pub preset: GraphicsPreset,
+    /// Shadow distance and cascade count of the [`Sun`] and [`Moon`]
+    pub shadows: Quality,
+    /// Atmosphere lookup texture sizes and sample counts
+    pub atmosphere: Quality,
+    pub antialiasing: Antialiasing,
+    pub bloom: bool,
+    pub fog: bool,
+    /// Fraction of the window resolution the scene is rendered at, UI stays sharp
+    pub render_scale: f32,
+}
+
+impl Default for Graphics {
+    fn default() -> Self {
+        GraphicsPreset::High.graphics()
+    }
+}
+
+impl Graphics {
+    /// Reports [`GraphicsPreset::Custom`] if the options no longer match the chosen preset
+    pub fn update_preset(&mut self) {
+        self.preset = GraphicsPreset::ALL
+            .into_iter()
+            .find(|p| {
+                let mut preset = p.graphics();
+                preset.preset = self.preset;
+                preset == *self
+            })
+            .unwrap_or(GraphicsPreset::Custom);
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GraphicsPreset {
+    Low,
+    Medium,
+    #[default]
+    High,
+    Custom,
+}
+
+impl GraphicsPreset {
+    /// Presets the quality button cycles through
+    pub const ALL: [Self; 3] = [Self::Low, Self::Medium, Self::High];
+
+    pub fn graphics(self) -> Graphics {
+        match self {
+            Self::Low => Graphics {
+                preset: self,
+                shadows: Quality::Low,
+                atmosphere: Quality::Low,
+                antialiasing: Antialiasing::Off,
+                bloom: false,
+                fog: false,
+                render_scale: 0.75,
+            },
+            Self::Medium => Graphics {
+                preset: self,
+                shadows: Quality::Medium,
+                atmosphere: Quality::Medium,
+                antialiasing: Antialiasing::Smaa,
+                bloom: true,
+                fog: true,
+                render_scale: 1.0,
+            },
+            Self::High | Self::Custom => Graphics {
+                preset: self,
+                shadows: Quality::High,
+                atmosphere: Quality::High,
+                antialiasing: Antialiasing::Msaa,
+                bloom: true,
+                fog: true,
+                render_scale: 1.0,
+            },
+        }
+    }
+
+    /// Next preset for the quality button, custom settings start over from the lowest
+    pub fn next(self) -> Self {
+        match self {
+            Self::Low => Self::Medium,
+            Self::Medium => Self::High,
+            Self::High | Self::Custom => Self::Low,
+        }
+    }
+}
+
+impl Display for GraphicsPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quality {
+    Low,
+    Medium,
+    High,
+}
+
+impl Quality {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Low => Self::Medium,
+            Self::Medium => Self::High,
+            Self::High => Self::Low,
+        }
+    }
+
+    pub fn shadow_distance(self) -> f32 {
+        match self {
+            Self::Low => 100.0,
+            Self::Medium => 250.0,
+            Self::High => 500.0,
+        }
+    }
+
+    pub fn shadow_cascades(self) -> usize {
+        match self {
+            Self::Low => 1,
+            Self::Medium => 2,
+            Self::High => 4,
+        }
+    }
+
+    /// Medium is bevy's default, high doubles the lookup textures for smoother gradients
+    pub fn atmosphere(self) -> AtmosphereSettings {
+        let default = AtmosphereSettings::default();
+        let (size, samples) = match self {
+            Self::Low => (0.5, 0.5),
+            Self::Medium => (1.0, 1.0),
+            Self::High => (2.0, 1.5),
+        };
+        let scale = |v: u32, by: f32| ((v as f32 * by) as u32).max(1);
+        AtmosphereSettings {
+            scene_units_to_m: 1.0,
+            aerial_view_lut_max_distance: 40_000.0, //  40 km for a vast scene
+            transmittance_lut_size: default.transmittance_lut_size.map(|v| scale(v, size)),
+            sky_view_lut_size: default.sky_view_lut_size.map(|v| scale(v, size)),
+            aerial_view_lut_size: default.aerial_view_lut_size.map(|v| scale(v, size)),
+            transmittance_lut_samples: scale(default.transmittance_lut_samples, samples),
+            multiscattering_lut_dirs: scale(default.multiscattering_lut_dirs, size),
+            multiscattering_lut_samples: scale(default.multiscattering_lut_samples, samples),
+            sky_view_lut_samples: scale(default.sky_view_lut_samples, samples),
+            aerial_view_lut_samples: scale(default.aerial_view_lut_samples, samples),
+            ..default
+        }
+    }
+}
+
+impl Display for Quality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Antialiasing {
+    Off,
+    /// 4x multisampling
+    Msaa,
+    Smaa,
+}
+
+impl Antialiasing {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Msaa,
+            Self::Msaa => Self::Smaa,
+            Self::Smaa => Self::Off,
+        }
+    }
+}
+
+impl Display for Antialiasing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::Msaa => write!(f, "MSAA 4x"),
+            Self::Smaa => write!(f, "SMAA"),
+        }
+    }
+}
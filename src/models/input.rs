@@ -1,4 +1,5 @@
 use super::*;
+use bevy::ecs::spawn::{SpawnIter, SpawnableList};
 
 pub fn plugin(app: &mut App) {
     app.add_plugins(EnhancedInputPlugin)
@@ -8,7 +9,8 @@ pub fn plugin(app: &mut App) {
         .add_observer(rm_modal_ctx)
         .add_observer(rm_player_ctx)
         .add_observer(add_modal_ctx)
-        .add_observer(add_player_ctx);
+        .add_observer(add_player_ctx)
+        .add_observer(rebind_player_ctx);
 }
 
 fn spawn_ctx(mut cmds: Commands) {
@@ -67,7 +69,7 @@ pub struct Escape;
 
 #[derive(InputAction)]
 #[action_output(Vec2)]
-struct NavigateModal;
+pub struct NavigateModal;
 
 #[derive(Debug, InputAction)]
 #[action_output(bool)]
@@ -83,12 +85,48 @@ pub struct LeftTab;
 
 pub fn add_player_ctx(
     on: Trigger<OnAdd, PlayerCtx>,
+    settings: Res<Settings>,
     mut commands: Commands,
-    // mut settings: ResMut<Settings>,
 ) {
     let mut e = commands.entity(on.target());
 
-    e.insert(actions!(PlayerCtx[
+    e.insert(player_actions(&settings.input_map));
+
+    // #[cfg(feature = "top_down")]
+    e.insert(actions!(ModalCtx[
+        (
+            Action::<ScrollZoom>::new(),
+            ActionSettings {
+                require_reset: true,
+                ..Default::default()
+            },
+                Bindings::spawn( Spawn(Binding::mouse_motion())),
+        ),
+        (
+            Action::<RotateToggle>::new(),
+            bindings![MouseButton::Right],
+        ),
+    ]));
+}
+
+/// Keybind editor applies new [`InputSettings`], swap the actions in place
+fn rebind_player_ctx(
+    _: Trigger<SettingsChanged>,
+    settings: Res<Settings>,
+    player: Query<Entity, With<PlayerCtx>>,
+    mut commands: Commands,
+) {
+    for entity in player.iter() {
+        commands
+            .entity(entity)
+            .despawn_related::<Actions<PlayerCtx>>()
+            .insert(player_actions(&settings.input_map));
+    }
+}
+
+/// Rebindable actions come from [`InputSettings`], camera and menu keys are fixed
+fn player_actions(input: &InputSettings) -> impl Bundle {
+    actions!(PlayerCtx[
         (
             Action::<Pan>::new(),
             ActionSettings {
@@ -105,27 +143,30 @@ pub fn add_player_ctx(
             Action::<Navigate>::new(),
             DeadZone::default(),
             Scale::splat(0.3),
-            Bindings::spawn(( Cardinal::wasd_keys(), Cardinal::arrow_keys(), Axial::left_stick() )),
+            Bindings::spawn((
+                KeyboardMovement::from(input),
+                GamepadMovement::from(&input.gamepad),
+            )),
         ),
         (
             Action::<Crouch>::new(),
-            bindings![KeyCode::ControlLeft, GamepadButton::East],
+            buttons(input.crouch, input.gamepad.crouch),
         ),
         (
             Action::<Jump>::new(),
-            bindings![KeyCode::Space, GamepadButton::South],
+            buttons(input.jump, input.gamepad.jump),
         ),
         (
             Action::<Dash>::new(),
-            bindings![KeyCode::AltLeft, GamepadButton::LeftTrigger],
+            buttons(input.dash, input.gamepad.dash),
         ),
         (
             Action::<Sprint>::new(),
-            bindings![KeyCode::ShiftLeft, GamepadButton::LeftThumb],
+            buttons(input.sprint, input.gamepad.sprint),
         ),
         (
             Action::<Attack>::new(),
-            bindings![MouseButton::Left, GamepadButton::RightTrigger2],
+            buttons(input.attack, input.gamepad.attack),
         ),
 
         (
@@ -144,23 +185,116 @@ pub fn add_player_ctx(
             },
             bindings![KeyCode::Escape, GamepadButton::Select],
         ),
-    ]));
+    ])
+}
 
-    // #[cfg(feature = "top_down")]
-    e.insert(actions!(ModalCtx[
-        (
-            Action::<ScrollZoom>::new(),
-            ActionSettings {
-                require_reset: true,
-                ..Default::default()
-            },
-                Bindings::spawn( Spawn(Binding::mouse_motion())),
-        ),
-        (
-            Action::<RotateToggle>::new(),
-            bindings![MouseButton::Right],
-        ),
-    ]));
+/// User bindings for a button action, keyboard and gamepad columns
+fn buttons(
+    bindings: [Binding; BINDINGS_COUNT],
+    pad: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+) -> impl Bundle {
+    let bindings = bindings.into_iter().filter(|b| *b != Binding::None);
+    Bindings::spawn((SpawnIter(bindings), GamepadButtons(pad)))
+}
+
+/// Spawns a gamepad binding, half axes are clamped to their part and flipped to `0..1`.
+/// `negate` flips the result once more, for directions pointing the other way.
+fn spawn_gamepad_binding(
+    world: &mut World,
+    entity: Entity,
+    binding: GamepadBinding,
+    negate: bool,
+) -> Option<EntityWorldMut<'_>> {
+    match binding {
+        GamepadBinding::None => None,
+        GamepadBinding::Button(button) => Some(world.spawn((
+            BindingOf(entity),
+            Binding::from(button),
+            Negate::splat(negate),
+        ))),
+        GamepadBinding::Axis { axis, positive } => Some(world.spawn((
+            BindingOf(entity),
+            Binding::from(axis),
+            if positive { Clamp::pos() } else { Clamp::neg() },
+            Negate::splat(positive == negate),
+        ))),
+    }
+}
+
+/// Gamepad columns of a button action, half axes need a dead zone to not fire on stick drift
+struct GamepadButtons([GamepadBinding; GAMEPAD_BINDINGS_COUNT]);
+
+impl SpawnableList<BindingOf> for GamepadButtons {
+    fn spawn(self, world: &mut World, entity: Entity) {
+        for binding in self.0 {
+            if let Some(mut e) = spawn_gamepad_binding(world, entity, binding, false) {
+                e.insert(DeadZone::default());
+            }
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        GAMEPAD_BINDINGS_COUNT
+    }
+}
+
+/// Movement from [`GamepadBindings`], every direction is a separate half axis or button
+struct GamepadMovement(GamepadBindings);
+
+impl From<&GamepadBindings> for GamepadMovement {
+    fn from(input: &GamepadBindings) -> Self {
+        Self(input.clone())
+    }
+}
+
+impl SpawnableList<BindingOf> for GamepadMovement {
+    fn spawn(self, world: &mut World, entity: Entity) {
+        let directions = [
+            (self.0.forward, false, true),
+            (self.0.right, false, false),
+            (self.0.backward, true, true),
+            (self.0.left, true, false),
+        ];
+        for (bindings, negate, vertical) in directions {
+            for binding in bindings {
+                if let Some(mut e) = spawn_gamepad_binding(world, entity, binding, negate)
+                    && vertical
+                {
+                    e.insert(SwizzleAxis::YXZ);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        4 * GAMEPAD_BINDINGS_COUNT
+    }
+}
+
+/// Movement keys from [`InputSettings`], spawned as a [`Cardinal`] per binding column
+struct KeyboardMovement([Cardinal<Binding, Binding, Binding, Binding>; BINDINGS_COUNT]);
+
+impl From<&InputSettings> for KeyboardMovement {
+    fn from(input: &InputSettings) -> Self {
+        Self(std::array::from_fn(|i| Cardinal {
+            north: input.forward[i],
+            east: input.right[i],
+            south: input.backward[i],
+            west: input.left[i],
+        }))
+    }
+}
+
+impl SpawnableList<BindingOf> for KeyboardMovement {
+    fn spawn(self, world: &mut World, entity: Entity) {
+        for cardinal in self.0 {
+            cardinal.spawn(world, entity);
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        4 * BINDINGS_COUNT
+    }
 }
 
 fn rm_player_ctx(on: Trigger<OnRemove, PlayerCtx>, mut commands: Commands) {
@@ -182,6 +316,9 @@ fn add_modal_ctx(on: Trigger<OnAdd, ModalCtx>, mut commands: Commands) {
                 },
                 Bindings::spawn((
                     Cardinal::wasd_keys(),
+                    Cardinal::arrow_keys(),
+                    Cardinal::dpad_buttons(),
+                    Axial::left_stick().with(DeadZone::default()),
                     Spawn((Binding::mouse_motion(),Scale::splat(0.1), Negate::all())),
                     Axial::right_stick().with((Scale::splat(2.0), Negate::x())) ,
                 )),
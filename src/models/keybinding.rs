@@ -1,24 +1,19 @@
 use super::*;
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
 
 /// Number of input columns.
 pub const BINDINGS_COUNT: usize = 3;
-const SETTINGS_PATH: &str = "target/settings.ron";
-const GAP: Val = Val::Px(10.0);
-const PADDING: UiRect = UiRect::all(Val::Px(15.0));
-const PANEL_BACKGROUND: BackgroundColor = BackgroundColor(Color::srgb(0.8, 0.8, 0.8));
+/// Number of gamepad input columns.
+pub const GAMEPAD_BINDINGS_COUNT: usize = 2;
 
-/// Keyboard and mouse settings.
+/// Keyboard, mouse and gamepad settings.
 ///
-/// Most games assign bindings for different input sources (keyboard + mouse, gamepads, etc.) separately or
-/// even only allow rebinding for keyboard and mouse.
-/// For example, gamepads use sticks for movement, which are bidirectional, so it doesn't make sense to assign
-/// actions like "forward" to [`GamepadAxis::LeftStickX`].
-///
-/// If you want to assign a specific part of the axis, such as the positive part of [`GamepadAxis::LeftStickX`],
-/// you need to create your own binding enum. However, this approach is mostly used in emulators rather than games.
-///
-/// So in this example we assign only keyboard and mouse bindings.
+/// Bindings for different input sources are assigned separately: keyboard and mouse go into the
+/// [`Binding`] columns, gamepads get their own [`GamepadBindings`] columns.
+/// Gamepads use sticks for movement, which are bidirectional, so it doesn't make sense to assign
+/// actions like "forward" to the whole [`GamepadAxis::LeftStickY`]. That's why gamepad columns use
+/// [`GamepadBinding`], which can address only the positive or negative part of an axis.
 #[derive(Resource, Reflect, Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct InputSettings {
@@ -31,6 +26,7 @@ pub struct InputSettings {
     pub sprint: [Binding; BINDINGS_COUNT],
     pub crouch: [Binding; BINDINGS_COUNT],
     pub attack: [Binding; BINDINGS_COUNT],
+    pub gamepad: GamepadBindings,
 }
 
 impl InputSettings {
@@ -44,6 +40,7 @@ impl InputSettings {
         self.sprint.fill(Binding::None);
         self.crouch.fill(Binding::None);
         self.attack.fill(Binding::None);
+        self.gamepad.clear();
     }
 }
 
@@ -71,6 +68,137 @@ impl Default for InputSettings {
             crouch: [KeyCode::ControlLeft.into(), Binding::None, Binding::None],
             sprint: [KeyCode::ShiftLeft.into(), Binding::None, Binding::None],
             attack: [MouseButton::Left.into(), Binding::None, Binding::None],
+            gamepad: GamepadBindings::default(),
+        }
+    }
+}
+
+/// Gamepad settings, same actions as [`InputSettings`].
+#[derive(Reflect, Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GamepadBindings {
+    pub forward: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub left: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub backward: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub right: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub jump: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub dash: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub sprint: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub crouch: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+    pub attack: [GamepadBinding; GAMEPAD_BINDINGS_COUNT],
+}
+
+impl GamepadBindings {
+    pub fn clear(&mut self) {
+        self.forward.fill(GamepadBinding::None);
+        self.left.fill(GamepadBinding::None);
+        self.backward.fill(GamepadBinding::None);
+        self.right.fill(GamepadBinding::None);
+        self.jump.fill(GamepadBinding::None);
+        self.dash.fill(GamepadBinding::None);
+        self.sprint.fill(GamepadBinding::None);
+        self.crouch.fill(GamepadBinding::None);
+        self.attack.fill(GamepadBinding::None);
+    }
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        Self {
+            forward: [
+                GamepadBinding::axis(GamepadAxis::LeftStickY, true),
+                GamepadButton::DPadUp.into(),
+            ],
+            left: [
+                GamepadBinding::axis(GamepadAxis::LeftStickX, false),
+                GamepadButton::DPadLeft.into(),
+            ],
+            backward: [
+                GamepadBinding::axis(GamepadAxis::LeftStickY, false),
+                GamepadButton::DPadDown.into(),
+            ],
+            right: [
+                GamepadBinding::axis(GamepadAxis::LeftStickX, true),
+                GamepadButton::DPadRight.into(),
+            ],
+            jump: [GamepadButton::South.into(), GamepadBinding::None],
+            dash: [GamepadButton::LeftTrigger.into(), GamepadBinding::None],
+            crouch: [GamepadButton::East.into(), GamepadBinding::None],
+            sprint: [GamepadButton::LeftThumb.into(), GamepadBinding::None],
+            attack: [GamepadButton::RightTrigger2.into(), GamepadBinding::None],
+        }
+    }
+}
+
+/// Gamepad input that can be assigned to an action.
+///
+/// Unlike [`Binding`] it can point to one half of an axis, e.g. only the positive part of
+/// [`GamepadAxis::LeftStickY`] for "forward".
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum GamepadBinding {
+    #[default]
+    None,
+    Button(GamepadButton),
+    Axis {
+        axis: GamepadAxis,
+        positive: bool,
+    },
+}
+
+impl GamepadBinding {
+    pub fn axis(axis: GamepadAxis, positive: bool) -> Self {
+        Self::Axis { axis, positive }
+    }
+}
+
+impl From<GamepadButton> for GamepadBinding {
+    fn from(button: GamepadButton) -> Self {
+        Self::Button(button)
+    }
+}
+
+impl Display for GamepadBinding {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Button(button) => write!(f, "{button:?}"),
+            Self::Axis { axis, positive } => {
+                write!(f, "{axis:?}{}", if *positive { "+" } else { "-" })
+            }
+        }
+    }
+}
+
+/// Whether a movement modifier is held down or pressed once to switch it on and off
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModifierMode {
+    #[default]
+    Hold,
+    Toggle,
+}
+
+impl ModifierMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Hold => Self::Toggle,
+            Self::Toggle => Self::Hold,
+        }
+    }
+}
+
+impl Display for ModifierMode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Hold => write!(f, "Hold"),
+            Self::Toggle => write!(f, "Toggle"),
         }
     }
 }
+
+/// [`ModifierMode`] per [`Locomotion`] modifier, toggles are easier on a gamepad
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Controls {
+    pub sprint: ModifierMode,
+    pub crouch: ModifierMode,
+}
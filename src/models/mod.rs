@@ -1,36 +1,67 @@
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
-use bevy_seedling::prelude::*;
 use serde::{Deserialize, Serialize};
 
+mod combat;
+mod display;
+mod emitter;
 mod event_dispatch;
 mod ext_traits;
+mod graphics;
 mod input;
 mod keybinding;
 mod palette;
 mod player;
 mod pre_load;
 mod primitives;
+// files only, the browser build has no save games
+#[cfg(not(target_arch = "wasm32"))]
+mod save;
 mod settings;
+mod soundtrack;
 mod states;
+mod storage;
+mod surface;
+mod time_of_day;
+mod weather;
 
+pub use combat::*;
+pub use display::*;
+pub use emitter::*;
 pub use event_dispatch::*;
 pub use ext_traits::*;
+pub use graphics::*;
 pub use input::*;
 pub use keybinding::*;
 pub use palette::*;
 pub use player::*;
 pub use pre_load::*;
 pub use primitives::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use save::*;
 pub use settings::*;
+pub use soundtrack::*;
 pub use states::*;
+pub use storage::*;
+pub use surface::*;
+pub use time_of_day::*;
+pub use weather::*;
 
 pub fn plugin(app: &mut App) {
     // register for skein
     app.add_plugins((
         primitives::plugin,
+        combat::plugin,
+        player::plugin,
+        storage::plugin,
+        surface::plugin,
         settings::plugin,
+        soundtrack::plugin,
+        display::plugin,
+        emitter::plugin,
         states::plugin,
+        time_of_day::plugin,
+        weather::plugin,
         input::plugin,
         event_dispatch::plugin,
     ));
@@ -50,9 +81,13 @@ pub enum AppSystems {
 pub enum Modal {
     Main,
     Settings,
+    #[cfg(not(target_arch = "wasm32"))]
+    Save,
+    #[cfg(not(target_arch = "wasm32"))]
+    Load,
 }
 
-#[derive(Reflect, Debug, Clone, Serialize, Deserialize)]
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SunCycle {
     DayNight,
     Nimbus,
@@ -1,6 +1,10 @@
 use super::*;
 use std::collections::HashMap;
 
+pub fn plugin(app: &mut App) {
+    app.register_type::<Stamina>().register_type::<Locomotion>();
+}
+
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct Player {
@@ -21,6 +25,92 @@ impl Default for Player {
     }
 }
 
+/// Movement modifier the player is in, only one at a time so sprint and crouch can't stack.
+/// [`Player::speed`] follows it through the [`Movement`] factors
+#[derive(Component, Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component)]
+pub enum Locomotion {
+    #[default]
+    Walk,
+    Sprint,
+    Crouch,
+}
+
+impl Locomotion {
+    pub fn speed(self, movement: &Movement) -> f32 {
+        match self {
+            Self::Walk => movement.speed,
+            Self::Sprint => movement.speed * movement.sprint_factor,
+            Self::Crouch => movement.speed * movement.crouch_factor,
+        }
+    }
+}
+
+/// Spent by sprinting and dashing, refills after [`StaminaConfig::regen_delay`] without use
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct Stamina {
+    pub current: f32,
+    pub max: f32,
+    /// Set when stamina runs out, sprint and dash stay locked until it refills
+    /// to [`StaminaConfig::recover`]
+    pub exhausted: bool,
+    /// Seconds since stamina was last spent, compared against [`StaminaConfig::regen_delay`]
+    pub since_spent: f32,
+}
+
+impl Stamina {
+    pub fn new(max: f32) -> Self {
+        Self {
+            current: max,
+            max,
+            exhausted: false,
+            since_spent: f32::INFINITY,
+        }
+    }
+
+    /// Back to full, as after a respawn
+    pub fn refill(&mut self) {
+        *self = Self::new(self.max);
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            self.current / self.max
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether `amount` can be spent right now
+    pub fn can_spend(&self, amount: f32) -> bool {
+        !self.exhausted && self.current >= amount
+    }
+
+    /// Spends up to `amount` and restarts the regeneration delay
+    pub fn spend(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+        self.since_spent = 0.0;
+        if self.current <= 0.0 {
+            self.exhausted = true;
+        }
+    }
+}
+
+/// Wall in front of the player, refreshed every frame by a shape cast
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(Component)]
+pub struct WallContact(pub Option<WallHit>);
+
+#[derive(Reflect, Clone, Copy, Debug)]
+pub struct WallHit {
+    pub entity: Entity,
+    pub point: Vec3,
+    /// Horizontal, pointing away from the wall
+    pub normal: Dir3,
+    pub climbable: bool,
+}
+
 #[derive(Component, Reflect, Default, Clone)]
 #[reflect(Component)]
 pub enum AnimationState {
@@ -39,4 +129,6 @@ pub enum AnimationState {
     WallSlide,
     WallJump,
     KnockBack,
+    Attack,
+    Death,
 }
@@ -1,5 +1,7 @@
+use super::{SurfaceMaterial, WeatherKind};
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect, Asset, Resource)]
 #[reflect(Resource)]
@@ -10,13 +12,20 @@ pub struct Config {
     pub player: PlayerConfig,
     pub settings: SettingsPreloaded,
     pub timers: Timers,
+    pub day_cycle: DayCycle,
+    pub weather: WeatherConfig,
+    pub music: MusicConfig,
+    pub footsteps: Footsteps,
 }
 
 #[derive(Resource, Debug, Clone, Serialize, Deserialize, Reflect)]
+#[serde(default)]
 pub struct Sound {
     pub general: f32,
     pub music: f32,
     pub sfx: f32,
+    pub ambience: f32,
+    pub ui: f32,
 }
 
 impl Default for Sound {
@@ -25,6 +34,8 @@ impl Default for Sound {
             general: 1.0,
             music: 0.5,
             sfx: 0.5,
+            ambience: 0.5,
+            ui: 0.5,
         }
     }
 }
@@ -34,16 +45,20 @@ pub struct Physics {
     pub distance_fog: bool,
     pub fog_directional_light_exponent: f32,
     pub fog_visibility: f32,
-    pub shadow_distance: f32,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
 pub struct PlayerConfig {
     pub movement: Movement,
+    pub melee: Melee,
     pub hitbox: Hitbox,
     pub zoom: (f32, f32),
     pub fov: f32,
     pub spawn_pos: (f32, f32, f32),
+    pub health: f32,
+    pub stamina: StaminaConfig,
+    /// Shove strength applied when taking damage
+    pub knockback: f32,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
@@ -60,6 +75,95 @@ pub struct Movement {
     pub sprint_factor: f32,
     pub crouch_factor: f32,
     pub idle_to_run_threshold: f32,
+    /// How far in front of the player walls are found
+    pub wall_distance: f32,
+    /// Fastest fall while sliding down a wall
+    pub wall_slide_speed: f32,
+    pub wall_jump_height: f32,
+    /// Horizontal speed away from the wall on a wall jump
+    pub wall_jump_push: f32,
+    /// Vertical speed on a [`Climbable`]
+    pub climb_speed: f32,
+    /// Dash speed, the dash covers `dash_distance` at it
+    pub dash_speed: f32,
+    pub walk: WalkTuning,
+    pub jump: JumpTuning,
+    pub crouch: CrouchTuning,
+}
+
+/// `TnuaBuiltinWalk` parameters, see the tnua platformer demo for what each one does
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct WalkTuning {
+    pub float_height: f32,
+    /// Slightly higher than `float_height` for a bit of "give"
+    pub cling_distance: f32,
+    /// Stronger spring for a more grounded feel
+    pub spring_strength: f32,
+    /// Slightly reduced dampening for a more responsive spring
+    pub spring_dampening: f32,
+    /// Higher for snappier movement starts and stops
+    pub acceleration: f32,
+    /// Some air control, but less than on the ground
+    pub air_acceleration: f32,
+    /// Slightly increased for a less floaty fall
+    pub free_fall_extra_gravity: f32,
+    /// Higher for a slightly faster righting response
+    pub tilt_offset_angvel: f32,
+    /// Acceleration to reach the target righting speed
+    pub tilt_offset_angacl: f32,
+    /// Higher for more responsive turning
+    pub turning_angvel: f32,
+}
+
+/// `TnuaBuiltinJump` parameters
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct JumpTuning {
+    pub height: f32,
+    /// Higher for a snappier, more immediate lift-off
+    pub takeoff_extra_gravity: f32,
+    /// Makes falling feel more impactful and less floaty
+    pub fall_extra_gravity: f32,
+    /// Applied when the button is released early, high enough for very short hops on a tap
+    pub shorten_extra_gravity: f32,
+    /// Lower starts the peak prevention sooner
+    pub peak_prevention_at_upward_velocity: f32,
+    /// Reduces "floatiness" at the jump's apex
+    pub peak_prevention_extra_gravity: f32,
+    /// Seconds of jump buffering, a press just before landing still jumps
+    pub reschedule_cooldown: f32,
+}
+
+/// `TnuaBuiltinCrouch` parameters
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct CrouchTuning {
+    pub float_offset: f32,
+    pub height_change_impulse_for_duration: f32,
+    pub height_change_impulse_limit: f32,
+}
+
+/// Stamina pool spent on sprint and dash, rates are per second
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct StaminaConfig {
+    pub max: f32,
+    pub regen: f32,
+    /// Seconds without spending before regeneration starts
+    pub regen_delay: f32,
+    pub sprint_drain: f32,
+    pub dash_cost: f32,
+    /// Share of `max` to refill before sprint and dash unlock after exhaustion
+    pub recover: f32,
+}
+
+/// Melee attack tuning, durations are in seconds
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct Melee {
+    pub damage: f32,
+    pub windup: f32,
+    pub active: f32,
+    pub recovery: f32,
+    /// Distance of the hitbox center in front of the attacker
+    pub reach: f32,
+    pub radius: f32,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
@@ -75,6 +179,145 @@ pub struct SettingsPreloaded {
 pub struct Timers {
     pub step: f32,
     pub jump: f32,
+    pub invulnerability: f32,
+    /// From death to respawn, the screen fades back in for the same time after
+    pub respawn: f32,
+    /// How long the player has to stay in or out of a [`Zone`] before the mood switches
+    pub mood_change: f32,
+    /// Crossfade between music tracks
+    pub music_fade: f32,
+    /// Silence between the end of a piece and the next one in the playlist
+    pub music_gap: f32,
+    /// How long the "now playing" toast stays up
+    pub now_playing: f32,
+    /// Interval between autosaves, zero turns the periodic autosave off
+    pub autosave: f32,
+}
+
+/// What drives [`MusicIntensity`], the contributions add up and are capped at 1
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct MusicConfig {
+    /// Intensity change per second while easing towards the target
+    pub intensity_rate: f32,
+    /// Inside a [`Combat`] zone
+    pub combat: f32,
+    /// Per living enemy within `enemy_radius`
+    pub enemy: f32,
+    pub enemy_radius: f32,
+    /// While the player sprints
+    pub sprint: f32,
+}
+
+/// Footstep samples per [`SurfaceMaterial`], as asset paths
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct Footsteps {
+    pub dirt: Vec<String>,
+    pub grass: Vec<String>,
+    pub stone: Vec<String>,
+    pub wood: Vec<String>,
+    pub metal: Vec<String>,
+    pub snow: Vec<String>,
+}
+
+impl Footsteps {
+    pub fn samples(&self, surface: SurfaceMaterial) -> &[String] {
+        match surface {
+            SurfaceMaterial::Dirt => &self.dirt,
+            SurfaceMaterial::Grass => &self.grass,
+            SurfaceMaterial::Stone => &self.stone,
+            SurfaceMaterial::Wood => &self.wood,
+            SurfaceMaterial::Metal => &self.metal,
+            SurfaceMaterial::Snow => &self.snow,
+        }
+    }
+
+    /// Every sample of every surface
+    pub fn all(&self) -> impl Iterator<Item = &String> {
+        let Self {
+            dirt,
+            grass,
+            stone,
+            wood,
+            metal,
+            snow,
+        } = self;
+        [dirt, grass, stone, wood, metal, snow]
+            .into_iter()
+            .flatten()
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct DayCycle {
+    /// Real seconds per in-game day, zero stops the clock
+    pub length: f32,
+    /// [`TimeOfDay::hours`] a new game starts at
+    pub start_hour: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct WeatherConfig {
+    /// Seconds to fade from one weather into the next
+    pub transition: f32,
+    /// Each weather lasts a random time between these, in seconds
+    pub min_duration: f32,
+    pub max_duration: f32,
+    /// Rain or snow particles around the camera at full strength
+    pub particles: usize,
+    pub states: HashMap<WeatherKind, WeatherParams>,
+}
+
+impl WeatherConfig {
+    pub fn params(&self, kind: WeatherKind) -> WeatherParams {
+        self.states.get(&kind).cloned().unwrap_or_default()
+    }
+}
+
+/// How a [`WeatherKind`] looks, blended during transitions
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct WeatherParams {
+    /// Relative chance of being picked next
+    pub weight: f32,
+    pub fog_density: f32,
+    pub fog_color: (f32, f32, f32),
+    /// Multiplies sun, moon and ambient light
+    pub light: f32,
+    /// Multiplies the atmosphere's mie scattering, hazier sky above one
+    pub haze: f32,
+    /// Share of [`WeatherConfig::particles`] spawned as rain and snow
+    pub rain: f32,
+    pub snow: f32,
+}
+
+impl Default for WeatherParams {
+    fn default() -> Self {
+        Self {
+            weight: 1.0,
+            fog_density: 0.002,
+            fog_color: (0.35, 0.48, 0.66),
+            light: 1.0,
+            haze: 1.0,
+            rain: 0.0,
+            snow: 0.0,
+        }
+    }
+}
+
+impl WeatherParams {
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let (a, b) = (self.fog_color, other.fog_color);
+        Self {
+            weight: self.weight.lerp(other.weight, t),
+            fog_density: self.fog_density.lerp(other.fog_density, t),
+            fog_color: (a.0.lerp(b.0, t), a.1.lerp(b.1, t), a.2.lerp(b.2, t)),
+            light: self.light.lerp(other.light, t),
+            haze: self.haze.lerp(other.haze, t),
+            rain: self.rain.lerp(other.rain, t),
+            snow: self.snow.lerp(other.snow, t),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
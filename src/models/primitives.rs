@@ -4,7 +4,10 @@ pub fn plugin(app: &mut App) {
     // register for skein
     app.register_type::<Zone>()
         .register_type::<Combat>()
-        .register_type::<Exploration>();
+        .register_type::<Exploration>()
+        .register_type::<ZonePriority>()
+        .register_type::<Damageable>()
+        .register_type::<Climbable>();
 }
 
 /// Macro to hide the derive trait boilerplate
@@ -26,9 +29,11 @@ markers!(
     Zone,
     Combat,
     Exploration,
-    // TODO: The idea is to create a boombox with spatial audio
-    // <https://github.com/bevyengine/bevy/blob/main/examples/audio/spatial_audio_3d.rs>
-    // Boombox,
+    // player can climb the collider, or any collider of the entity
+    Climbable,
+    // combat: anything that can be hit by a melee attack
+    Damageable,
+    Dead,
     SunCycleLabel,
     // user input contexts
     GlobalCtx,
@@ -40,26 +45,31 @@ markers!(
     GameplayUi,
     PauseIcon,
     MuteIcon,
+    NowPlayingToast,
+    StaminaBar,
     MenuModal,
+    SlotsModal,
     // settings
     SettingsModal,
     TabBar,
     TabContent,
     DisabledButton,
-    // Slider,
-    // SliderThumb,
+    SliderTrack,
+    SliderThumb,
+    SliderLabel,
     // Checkbox,
-    GeneralVolumeLabel,
-    MusicVolumeLabel,
-    SfxVolumeLabel,
     SaveSettingsLabel,
-    VsyncLabel,
-    FovLabel,
     // animations
     FadeIn,
     FadeOut
 );
 
+/// Priority of a [`Zone`], when zones overlap the highest one sets the mood.
+/// Without it [`Combat`] zones win over [`Exploration`] ones.
+#[derive(Component, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Component)]
+pub struct ZonePriority(pub i32);
+
 #[cfg(feature = "dev_native")]
 markers!(DiagnosticsLabel, DebugUiLabel);
 
@@ -72,18 +82,4 @@ macro_rules! timers {
         )*
     };
 }
-timers!(JumpTimer, StepTimer);
-
-// macro_rules! sliders {
-//   ( $( $name:ident ),* ) => {
-//         $(
-//             #[derive(Component, Reflect, Debug)]
-//             #[reflect(Component)]
-//             pub struct $name{
-//                 pub current: f32
-//             }
-//         )*
-//     };
-// }
-//
-// sliders!(SliderGeneral, SliderMusic, SliderSfx);
+timers!(JumpTimer, StepTimer, Invulnerable, RespawnTimer, ToastTimer);
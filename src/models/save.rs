@@ -0,0 +1,199 @@
+//! Save games: named slots stored as RON files next to the user's settings, see [`settings_path`]
+use super::*;
+use bevy::reflect::FromType;
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+/// Bumped whenever [`SaveData`] changes shape, older saves are rejected
+pub const SAVE_VERSION: u32 = 1;
+/// Slots offered by the save and load menus
+pub const SAVE_SLOTS: [&str; 3] = ["slot_1", "slot_2", "slot_3"];
+pub const AUTOSAVE_SLOT: &str = "autosave";
+/// Older autosaves kept as `autosave_1` (latest) to `autosave_N`
+pub const AUTOSAVE_BACKUPS: usize = 3;
+const SAVE_PREFIX: &str = "save_";
+/// Autosaves are written from a background task, keep rotations from interleaving
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Type data for reflected components that should go into save games.
+///
+/// Opt in with `#[reflect(Component, Save)]`, the component is then stored for the player
+/// and for every entity with a [`Name`].
+#[derive(Clone)]
+pub struct ReflectSave;
+
+impl<T: Component + Reflect> FromType<T> for ReflectSave {
+    fn from_type() -> Self {
+        Self
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SaveError {
+    #[error("could not access save file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse save file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("could not serialize save: {0}")]
+    Serialize(#[from] ron::Error),
+    #[error("save version {found} does not match the game's version {SAVE_VERSION}")]
+    Version { found: u32 },
+    #[error("save is missing: {0}")]
+    Missing(&'static str),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaveData {
+    pub version: u32,
+    /// Seconds since the unix epoch, "Continue" picks the latest save
+    pub timestamp: u64,
+    pub player: PlayerSave,
+    pub mood: MoodType,
+    /// [`TimeOfDay::sun_angle`]
+    pub sun_angle: f32,
+    /// Components marked with [`ReflectSave`] as RON, keyed by entity [`Name`]
+    pub entities: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlayerSave {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub speed: f32,
+    /// Components marked with [`ReflectSave`] as RON
+    pub components: Vec<String>,
+}
+
+/// Version and time only, read first so saves of a different shape fail with a clear error
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+    timestamp: u64,
+}
+
+/// Slot name and time of an existing save
+#[derive(Debug, Clone)]
+pub struct SaveInfo {
+    pub slot: String,
+    pub timestamp: u64,
+}
+
+impl SaveInfo {
+    /// Human readable age of the save, e.g. "5 min ago"
+    pub fn age(&self) -> String {
+        let secs = now().saturating_sub(self.timestamp);
+        match secs {
+            0..60 => "just now".to_string(),
+            60..3600 => format!("{} min ago", secs / 60),
+            3600..86400 => format!("{} h ago", secs / 3600),
+            _ => format!("{} days ago", secs / 86400),
+        }
+    }
+}
+
+impl SaveData {
+    pub fn new(player: PlayerSave, mood: MoodType, sun_angle: f32) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            timestamp: now(),
+            player,
+            mood,
+            sun_angle,
+            entities: BTreeMap::new(),
+        }
+    }
+
+    pub fn path(slot: &str) -> PathBuf {
+        saves_dir().join(format!("{SAVE_PREFIX}{slot}.ron"))
+    }
+
+    pub fn read(slot: &str) -> Result<Self, SaveError> {
+        let content = fs::read_to_string(Self::path(slot))?;
+        let header: SaveHeader = ron::from_str(&content)?;
+        if header.version != SAVE_VERSION {
+            return Err(SaveError::Version {
+                found: header.version,
+            });
+        }
+        Ok(ron::from_str(&content)?)
+    }
+
+    pub fn save(&self, slot: &str) -> Result<(), SaveError> {
+        Self::write(slot, &self.to_ron()?, 0)?;
+        Ok(())
+    }
+
+    pub fn to_ron(&self) -> Result<String, SaveError> {
+        Ok(ron::ser::to_string_pretty(self, Default::default())?)
+    }
+
+    /// Writes already serialized save into the slot, shifting the previous one into
+    /// `{slot}_1`, `{slot}_1` into `{slot}_2` and so on up to `backups`.
+    ///
+    /// The new save goes to a temporary file first, so a failed write leaves the old save intact.
+    pub fn write(slot: &str, content: &str, backups: usize) -> io::Result<()> {
+        let _lock = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let path = Self::path(slot);
+        let tmp = path.with_extension("ron.tmp");
+        fs::write(&tmp, content)?;
+
+        if backups > 0 && path.exists() {
+            for i in (1..backups).rev() {
+                let from = Self::path(&format!("{slot}_{i}"));
+                if from.exists() {
+                    fs::rename(from, Self::path(&format!("{slot}_{}", i + 1)))?;
+                }
+            }
+            fs::rename(&path, Self::path(&format!("{slot}_1")))?;
+        }
+        fs::rename(tmp, path)
+    }
+
+    /// All readable saves with a matching version, latest first
+    pub fn list() -> Vec<SaveInfo> {
+        let Ok(dir) = fs::read_dir(saves_dir()) else {
+            return vec![];
+        };
+        let mut saves: Vec<SaveInfo> = dir
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let slot = name.strip_prefix(SAVE_PREFIX)?.strip_suffix(".ron")?;
+                let content = fs::read_to_string(entry.path()).ok()?;
+                let header: SaveHeader = ron::from_str(&content).ok()?;
+                (header.version == SAVE_VERSION).then(|| SaveInfo {
+                    slot: slot.to_string(),
+                    timestamp: header.timestamp,
+                })
+            })
+            .collect();
+        saves.sort_by_key(|s| Reverse(s.timestamp));
+        saves
+    }
+
+    /// Slot of the most recent save
+    pub fn latest() -> Option<String> {
+        Self::list().into_iter().next().map(|s| s.slot)
+    }
+}
+
+fn saves_dir() -> PathBuf {
+    settings_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -1,6 +1,6 @@
 use super::*;
 use serde::Deserialize;
-use std::{error::Error, fs};
+use std::{env, error::Error, path::PathBuf};
 
 pub fn plugin(app: &mut App) {
     app.init_resource::<Settings>().init_resource::<ActiveTab>();
@@ -10,61 +10,247 @@ pub fn plugin(app: &mut App) {
     );
 }
 
-pub const SETTINGS_PATH: &str = "assets/settings.ron";
+/// Shipped defaults from `assets/settings.ron`, only used when the user has no settings yet.
+/// Embedded so they are there on every platform.
+pub const DEFAULT_SETTINGS: &str = include_str!("../../assets/settings.ron");
+/// Bump together with a new step in [`migrate`] whenever [`Settings`] changes shape
+pub const SETTINGS_VERSION: u32 = 1;
+const SETTINGS_FILE: &str = "settings.ron";
+/// Where older builds saved user settings, imported into [`settings_path`] on first run
+pub const LEGACY_SETTINGS_PATH: &str = "assets/settings.ron";
+
+/// User settings file in the per-user config directory:
+/// - Linux: `$XDG_CONFIG_HOME/<game>` or `~/.config/<game>`
+/// - macOS: `~/Library/Application Support/<game>`
+/// - Windows: `%APPDATA%\<game>`
+///
+/// Falls back to the working directory if none of those can be found.
+pub fn settings_path() -> PathBuf {
+    config_dir()
+        .map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+        .unwrap_or_default()
+        .join(SETTINGS_FILE)
+}
+
+fn config_dir() -> Option<PathBuf> {
+    let var = |name| {
+        env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+    if cfg!(target_os = "windows") {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_CONFIG_HOME").or_else(|| var("HOME").map(|home| home.join(".config")))
+    }
+}
 
 #[derive(Resource, Reflect, Deserialize, Serialize, Debug, Clone)]
 #[reflect(Resource)]
+#[serde(default)]
 pub struct Settings {
+    /// Schema version of the file, see [`migrate`]
+    pub version: u32,
     // audio
     pub sound: Sound,
     // video
     pub fov: f32,
     pub sun_cycle: SunCycle,
-    // keybindings
+    pub graphics: Graphics,
+    pub display: DisplaySettings,
+    // controls
+    pub controls: Controls,
     pub input_map: InputSettings,
 }
 
 impl Settings {
-    pub fn music(&self) -> Volume {
-        Volume::Linear(self.sound.general * self.sound.music)
-    }
+    /// Reads user settings or the shipped defaults, upgrading older ones to [`SETTINGS_VERSION`].
+    /// Upgraded user settings are written back right away.
+    pub fn read(storage: &mut dyn SettingsStorage) -> Result<Self, Box<dyn Error>> {
+        let Some(content) = storage.read()? else {
+            return Ok(migrate(DEFAULT_SETTINGS)?.0);
+        };
 
-    pub fn sfx(&self) -> Volume {
-        Volume::Linear(self.sound.general * self.sound.sfx)
-    }
-
-    pub fn read() -> Result<Self, Box<dyn Error>> {
-        let content = fs::read_to_string(SETTINGS_PATH)?;
-        let settings = ron::from_str(&content).unwrap_or_default();
+        let (settings, from) = migrate(&content)?;
+        if from != SETTINGS_VERSION {
+            info!("upgraded settings from version {from} to {SETTINGS_VERSION}");
+            settings.save(storage)?;
+        }
         Ok(settings)
     }
 
-    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+    pub fn save(&self, storage: &mut dyn SettingsStorage) -> Result<(), Box<dyn Error>> {
         let content = ron::ser::to_string_pretty(self, Default::default())?;
-        fs::write(SETTINGS_PATH, content)?;
-        Ok(())
+        storage.write(&content)
     }
 }
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            version: SETTINGS_VERSION,
             sun_cycle: SunCycle::DayNight,
             sound: Sound::default(),
             fov: 45.0, // bevy default
+            graphics: Graphics::default(),
+            display: DisplaySettings::default(),
+            controls: Controls::default(),
             input_map: InputSettings::default(),
         }
     }
 }
 
-fn inject_settings_from_cfg(mut commands: Commands) {
-    let settings = match Settings::read() {
+#[derive(Deserialize)]
+struct SettingsHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Schema version of serialized settings, `None` if they don't parse at all
+pub fn settings_version(content: &str) -> Option<u32> {
+    ron::from_str::<SettingsHeader>(content)
+        .ok()
+        .map(|header| header.version)
+}
+
+/// Version 0: no `version` field. Key bindings were saved as `input_map`,
+/// the shipped defaults called them `keybind`.
+#[derive(Deserialize)]
+#[serde(default)]
+struct SettingsV0 {
+    sound: Sound,
+    fov: f32,
+    sun_cycle: SunCycle,
+    #[serde(alias = "input_map")]
+    keybind: InputSettingsV0,
+}
+
+impl Default for SettingsV0 {
+    fn default() -> Self {
+        let settings = Settings::default();
+        Self {
+            sound: settings.sound,
+            fov: settings.fov,
+            sun_cycle: settings.sun_cycle,
+            keybind: InputSettingsV0::default(),
+        }
+    }
+}
+
+impl From<SettingsV0> for Settings {
+    fn from(v0: SettingsV0) -> Self {
+        Self {
+            version: 1,
+            sound: v0.sound,
+            fov: v0.fov,
+            sun_cycle: v0.sun_cycle,
+            graphics: Graphics::default(),
+            display: DisplaySettings::default(),
+            controls: Controls::default(),
+            input_map: v0.keybind.into(),
+        }
+    }
+}
+
+/// Version 0 bindings had no gamepad columns
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct InputSettingsV0 {
+    forward: BindingsV0,
+    left: BindingsV0,
+    backward: BindingsV0,
+    right: BindingsV0,
+    jump: BindingsV0,
+    dash: BindingsV0,
+    sprint: BindingsV0,
+    crouch: BindingsV0,
+    attack: BindingsV0,
+}
+
+/// Bindings of one action. Saved files hold fixed size arrays, written by RON as tuples,
+/// while the shipped defaults used lists of any length, so both are accepted.
+#[derive(Default)]
+struct BindingsV0(Vec<Binding>);
+
+impl<'de> Deserialize<'de> for BindingsV0 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct BindingsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BindingsVisitor {
+            type Value = BindingsV0;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a list or tuple of bindings")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut bindings = vec![];
+                while let Some(binding) = seq.next_element()? {
+                    bindings.push(binding);
+                }
+                Ok(BindingsV0(bindings))
+            }
+        }
+
+        deserializer.deserialize_any(BindingsVisitor)
+    }
+}
+
+impl From<InputSettingsV0> for InputSettings {
+    fn from(v0: InputSettingsV0) -> Self {
+        let defaults = InputSettings::default();
+        // missing actions keep their defaults, the rest is padded to the column count
+        let columns = |BindingsV0(old): BindingsV0, default: [Binding; BINDINGS_COUNT]| {
+            if old.is_empty() {
+                return default;
+            }
+            std::array::from_fn(|i| old.get(i).copied().unwrap_or(Binding::None))
+        };
+        Self {
+            forward: columns(v0.forward, defaults.forward),
+            left: columns(v0.left, defaults.left),
+            backward: columns(v0.backward, defaults.backward),
+            right: columns(v0.right, defaults.right),
+            jump: columns(v0.jump, defaults.jump),
+            dash: columns(v0.dash, defaults.dash),
+            sprint: columns(v0.sprint, defaults.sprint),
+            crouch: columns(v0.crouch, defaults.crouch),
+            attack: columns(v0.attack, defaults.attack),
+            gamepad: defaults.gamepad,
+        }
+    }
+}
+
+/// Parses settings of any known version and runs them through the migration chain.
+/// Missing fields are filled from defaults. Returns the settings and the version they came from.
+fn migrate(content: &str) -> Result<(Settings, u32), Box<dyn Error>> {
+    let SettingsHeader { version } = ron::from_str(content)?;
+    let settings = match version {
+        0 => Settings::from(ron::from_str::<SettingsV0>(content)?),
+        // next: `1 => Settings::from(ron::from_str::<SettingsV1>(content)?)` and so on,
+        // each older version converts into the one after it
+        SETTINGS_VERSION => ron::from_str(content)?,
+        newer => return Err(format!("settings version {newer} is newer than the game").into()),
+    };
+    Ok((settings, version))
+}
+
+fn inject_settings_from_cfg(mut commands: Commands, mut store: ResMut<SettingsStore>) {
+    let settings = match Settings::read(store.0.as_mut()) {
         Ok(settings) => {
-            info!("loaded settings from '{SETTINGS_PATH}'");
+            info!("loaded settings from '{}'", store.location());
             settings
         }
         Err(e) => {
-            info!("unable to load settings from '{SETTINGS_PATH}', switching to defaults: {e}");
+            warn!(
+                "unable to load settings from '{}', switching to defaults: {e}",
+                store.location()
+            );
             Default::default()
         }
     };
@@ -81,5 +267,18 @@ pub enum UiTab {
     Keybindings,
 }
 
+impl UiTab {
+    pub const ALL: [Self; 3] = [Self::Audio, Self::Video, Self::Keybindings];
+
+    /// Tab `offset` places to the right, wrapping around
+    pub fn cycle(self, offset: isize) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|t| *t == self)
+            .unwrap_or_default() as isize;
+        Self::ALL[(i + offset).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ActiveTab(pub UiTab);
@@ -0,0 +1,128 @@
+//! Music pieces per [`MoodType`], each made of synchronized stems faded in by
+//! [`MusicIntensity`]. Described in `assets/soundtrack.ron`.
+use super::*;
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy_seedling::sample::Sample;
+use std::collections::HashMap;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<MusicIntensity>()
+        .init_resource::<MusicIntensity>()
+        .init_resource::<StemSamples>()
+        .add_systems(
+            PreUpdate,
+            load_stem_samples.run_if(resource_exists_and_changed::<Soundtrack>),
+        );
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect, Asset, Resource)]
+#[reflect(Resource)]
+pub struct Soundtrack {
+    pub moods: HashMap<MoodType, Vec<Piece>>,
+}
+
+impl Soundtrack {
+    pub fn pieces(&self, mood: &MoodType) -> &[Piece] {
+        self.moods.get(mood).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+/// One piece of music, its stems start together and play once, then the playlist moves on
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct Piece {
+    pub name: String,
+    pub stems: Vec<Stem>,
+}
+
+/// A layer of a [`Piece`], silent below `from` intensity and at full volume from `to`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Reflect)]
+pub struct Stem {
+    /// Asset path of the sample
+    pub sample: String,
+    #[serde(default)]
+    pub from: f32,
+    #[serde(default)]
+    pub to: f32,
+}
+
+impl Stem {
+    pub fn gain(&self, intensity: f32) -> f32 {
+        if self.to <= self.from {
+            (intensity >= self.from) as u8 as f32
+        } else {
+            ((intensity - self.from) / (self.to - self.from)).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// How intense the music is, `0.0..=1.0`. Eases towards `target`, see [`MusicConfig`].
+#[derive(Resource, Reflect, Debug, Clone, Default)]
+#[reflect(Resource)]
+pub struct MusicIntensity {
+    pub current: f32,
+    pub target: f32,
+}
+
+/// Sample handles of every stem in the [`Soundtrack`], by asset path
+#[derive(Resource, Default)]
+pub struct StemSamples(pub HashMap<String, Handle<Sample>>);
+
+impl StemSamples {
+    /// Handles of the piece's stems, `None` until all of them are done loading so they start in sync.
+    /// Stems that failed to load are left out.
+    pub fn piece<'a>(
+        &self,
+        piece: &'a Piece,
+        server: &AssetServer,
+    ) -> Option<Vec<(&'a Stem, Handle<Sample>)>> {
+        let states = piece
+            .stems
+            .iter()
+            .map(|stem| {
+                let handle = self.0.get(&stem.sample)?;
+                let state = server.get_recursive_dependency_load_state(handle)?;
+                Some((handle, state))
+            })
+            .collect::<Vec<_>>();
+        if states.iter().flatten().any(|(_, state)| {
+            matches!(
+                state,
+                RecursiveDependencyLoadState::NotLoaded | RecursiveDependencyLoadState::Loading
+            )
+        }) {
+            return None;
+        }
+
+        let mut ready = Vec::new();
+        for (stem, state) in piece.stems.iter().zip(states) {
+            match state {
+                Some((handle, RecursiveDependencyLoadState::Loaded)) => {
+                    ready.push((stem, handle.clone()))
+                }
+                Some((_, RecursiveDependencyLoadState::Failed(err))) => {
+                    warn!(
+                        "music stem {} failed to load, skipping it: {err}",
+                        stem.sample
+                    )
+                }
+                _ => warn!("music stem {} was never loaded, skipping it", stem.sample),
+            }
+        }
+        Some(ready)
+    }
+}
+
+fn load_stem_samples(
+    soundtrack: Res<Soundtrack>,
+    server: Res<AssetServer>,
+    mut samples: ResMut<StemSamples>,
+) {
+    samples.0 = soundtrack
+        .moods
+        .values()
+        .flatten()
+        .flat_map(|piece| &piece.stems)
+        .map(|stem| (stem.sample.clone(), server.load(&stem.sample)))
+        .collect();
+    info!("loading {} music stems", samples.0.len());
+}
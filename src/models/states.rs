@@ -67,7 +67,7 @@ pub struct Moods {
     pub inner: HashMap<MoodType, Entity>,
 }
 
-#[derive(Default, Clone, Eq, PartialEq, Debug, Hash, Reflect)]
+#[derive(Default, Clone, Eq, PartialEq, Debug, Hash, Reflect, Serialize, Deserialize)]
 pub enum MoodType {
     #[default]
     Exploration,
@@ -0,0 +1,215 @@
+//! Where [`Settings`] live between runs: a file on native, `localStorage` in the browser
+//! and memory for native runs of the `web` feature
+use super::*;
+use std::{error::Error, fs, path::PathBuf};
+
+pub fn plugin(app: &mut App) {
+    app.insert_resource(SettingsStore::platform());
+}
+
+/// Backend for the serialized [`Settings`]
+pub trait SettingsStorage: Send + Sync {
+    /// Stored content, `None` if nothing was saved yet
+    fn read(&self) -> Result<Option<String>, Box<dyn Error>>;
+    fn write(&mut self, content: &str) -> Result<(), Box<dyn Error>>;
+    /// Where the settings go, for logs
+    fn location(&self) -> String;
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct SettingsStore(pub Box<dyn SettingsStorage>);
+
+impl SettingsStore {
+    /// `localStorage` on wasm, memory for native `web` builds and the config dir otherwise
+    pub fn platform() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        return Self(Box::new(LocalStorage::new(env!("CARGO_PKG_NAME"))));
+        #[cfg(all(feature = "web", not(target_arch = "wasm32")))]
+        return Self(Box::new(MemoryStorage::default()));
+        #[cfg(not(any(feature = "web", target_arch = "wasm32")))]
+        return Self(Box::new(
+            FsStorage::new(settings_path()).with_legacy(LEGACY_SETTINGS_PATH.into()),
+        ));
+    }
+}
+
+/// Settings file, see [`settings_path`]
+pub struct FsStorage {
+    path: PathBuf,
+    /// Unversioned settings from older builds, read while `path` doesn't exist yet
+    legacy: Option<PathBuf>,
+}
+
+impl FsStorage {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, legacy: None }
+    }
+
+    pub fn with_legacy(mut self, legacy: PathBuf) -> Self {
+        self.legacy = Some(legacy);
+        self
+    }
+}
+
+impl SettingsStorage for FsStorage {
+    fn read(&self) -> Result<Option<String>, Box<dyn Error>> {
+        if self.path.exists() {
+            return Ok(Some(fs::read_to_string(&self.path)?));
+        }
+        // migrating them writes them to `path`, so this happens once
+        if let Some(legacy) = &self.legacy
+            && legacy.exists()
+        {
+            let content = fs::read_to_string(legacy)?;
+            if settings_version(&content) == Some(0) {
+                info!("importing settings from '{}'", legacy.display());
+                return Ok(Some(content));
+            }
+        }
+        Ok(None)
+    }
+
+    fn write(&mut self, content: &str) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+}
+
+/// Keeps settings for the lifetime of the app only
+#[derive(Default)]
+pub struct MemoryStorage {
+    content: Option<String>,
+}
+
+impl SettingsStorage for MemoryStorage {
+    fn read(&self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(self.content.clone())
+    }
+
+    fn write(&mut self, content: &str) -> Result<(), Box<dyn Error>> {
+        self.content = Some(content.to_string());
+        Ok(())
+    }
+
+    fn location(&self) -> String {
+        "memory".to_string()
+    }
+}
+
+/// Browser `localStorage` entry
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+    key: &'static str,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    pub fn new(key: &'static str) -> Self {
+        Self { key }
+    }
+
+    fn storage() -> Result<web_sys::Storage, Box<dyn Error>> {
+        web_sys::window()
+            .ok_or("no window")?
+            .local_storage()
+            .map_err(|e| format!("{e:?}"))?
+            .ok_or_else(|| "localStorage is not available".into())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl SettingsStorage for LocalStorage {
+    fn read(&self) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(Self::storage()?
+            .get_item(self.key)
+            .map_err(|e| format!("{e:?}"))?)
+    }
+
+    fn write(&mut self, content: &str) -> Result<(), Box<dyn Error>> {
+        Self::storage()?
+            .set_item(self.key, content)
+            .map_err(|e| format!("{e:?}"))?;
+        Ok(())
+    }
+
+    fn location(&self) -> String {
+        format!("localStorage['{}']", self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_storage_round_trips_settings() {
+        let mut storage = MemoryStorage::default();
+        assert!(storage.read().unwrap().is_none());
+
+        let settings = Settings {
+            fov: 70.0,
+            sun_cycle: SunCycle::Nimbus,
+            sound: Sound {
+                music: 0.25,
+                ..default()
+            },
+            ..default()
+        };
+        settings.save(&mut storage).unwrap();
+
+        let loaded = Settings::read(&mut storage).unwrap();
+        assert_eq!(loaded.version, SETTINGS_VERSION);
+        assert_eq!(loaded.fov, 70.0);
+        assert_eq!(loaded.sound.music, 0.25);
+        assert_eq!(loaded.sun_cycle, SunCycle::Nimbus);
+    }
+
+    #[test]
+    fn migrated_settings_are_written_back() {
+        let jump = ron::to_string(&Binding::from(KeyCode::KeyK)).unwrap();
+        let v0 = format!(
+            "(sound: (general: 0.8, music: 0.2, sfx: 0.3), fov: 60.0, sun_cycle: Nimbus, \
+             keybind: (jump: [{jump}]))"
+        );
+        let mut storage = MemoryStorage { content: Some(v0) };
+
+        let settings = Settings::read(&mut storage).unwrap();
+        assert_eq!(settings.fov, 60.0);
+        assert_eq!(settings.input_map.jump[0], Binding::from(KeyCode::KeyK));
+        // actions missing from the old file keep their defaults
+        assert_eq!(settings.input_map.dash, InputSettings::default().dash);
+
+        let stored = storage
+            .read()
+            .unwrap()
+            .expect("upgraded settings are saved");
+        let upgraded: Settings = ron::from_str(&stored).unwrap();
+        assert_eq!(upgraded.version, SETTINGS_VERSION);
+        assert_eq!(upgraded.input_map.jump[0], Binding::from(KeyCode::KeyK));
+        assert_eq!(upgraded.sound.general, 0.8);
+    }
+
+    #[test]
+    fn settings_saved_by_older_builds_keep_their_bindings() {
+        let jump = ron::to_string(&Binding::from(KeyCode::KeyK)).unwrap();
+        let none = ron::to_string(&Binding::None).unwrap();
+        // older builds saved fixed size arrays under the field name
+        let v0 = format!(
+            "(sound: (general: 1.0, music: 0.1, sfx: 0.5), fov: 45.0, sun_cycle: DayNight, \
+             input_map: (jump: ({jump}, {none}, {none})))"
+        );
+        let mut storage = MemoryStorage { content: Some(v0) };
+
+        let settings = Settings::read(&mut storage).unwrap();
+        assert_eq!(settings.input_map.jump[0], Binding::from(KeyCode::KeyK));
+        assert_eq!(settings.input_map.jump[1], Binding::None);
+        assert_eq!(settings.input_map.dash, InputSettings::default().dash);
+    }
+}
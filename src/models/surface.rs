@@ -0,0 +1,73 @@
+//! Ground types under the player, attached to colliders in Blender through skein
+use super::*;
+use bevy_seedling::sample::Sample;
+use std::collections::HashMap;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<SurfaceMaterial>()
+        .init_resource::<StepSamples>()
+        .add_systems(
+            PreUpdate,
+            load_step_samples.run_if(resource_exists_and_changed::<Config>),
+        );
+}
+
+/// What a collider is made of, picks the [`Footsteps`] played on it.
+/// Can be set on the collider itself or any of its ancestors.
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, Default)]
+pub enum SurfaceMaterial {
+    #[default]
+    Dirt,
+    Grass,
+    Stone,
+    Wood,
+    Metal,
+    Snow,
+}
+
+impl SurfaceMaterial {
+    /// Playback speed of steps on this surface, so surfaces sharing samples still sound apart
+    pub fn pitch(self) -> f64 {
+        match self {
+            Self::Dirt | Self::Snow => 1.0,
+            Self::Grass => 0.9,
+            Self::Stone => 1.1,
+            Self::Wood => 0.8,
+            Self::Metal => 1.3,
+        }
+    }
+
+    /// Loudness of steps on this surface relative to the sfx volume
+    pub fn volume(self) -> f32 {
+        match self {
+            Self::Dirt | Self::Snow => 1.0,
+            Self::Grass => 0.7,
+            Self::Stone | Self::Wood => 1.1,
+            Self::Metal => 1.3,
+        }
+    }
+}
+
+/// Sample handles of the [`Footsteps`] in the [`Config`], by asset path
+#[derive(Resource, Default)]
+pub struct StepSamples(pub HashMap<String, Handle<Sample>>);
+
+impl StepSamples {
+    /// Handles of the footsteps for `surface`
+    pub fn surface(&self, footsteps: &Footsteps, surface: SurfaceMaterial) -> Vec<Handle<Sample>> {
+        footsteps
+            .samples(surface)
+            .iter()
+            .filter_map(|path| self.0.get(path).cloned())
+            .collect()
+    }
+}
+
+fn load_step_samples(cfg: Res<Config>, server: Res<AssetServer>, mut samples: ResMut<StepSamples>) {
+    samples.0 = cfg
+        .footsteps
+        .all()
+        .map(|path| (path.clone(), server.load(path)))
+        .collect();
+}
@@ -0,0 +1,71 @@
+use super::*;
+use std::f32::consts::TAU;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<TimeOfDay>()
+        .init_resource::<TimeOfDay>();
+}
+
+/// In-game clock, advanced by the day cycle at [`DayCycle::length`] seconds per day
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct TimeOfDay {
+    /// Hours since midnight, `0.0..24.0`
+    pub hours: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self { hours: 12.0 }
+    }
+}
+
+impl TimeOfDay {
+    pub fn new(hours: f32) -> Self {
+        Self {
+            hours: hours.rem_euclid(24.0),
+        }
+    }
+
+    /// Sun rotation around the sky: 0 at sunrise (6:00), a quarter turn at noon
+    pub fn sun_angle(&self) -> f32 {
+        (self.hours / 24.0 - 0.25).rem_euclid(1.0) * TAU
+    }
+
+    pub fn from_sun_angle(angle: f32) -> Self {
+        Self::new((angle / TAU + 0.25) * 24.0)
+    }
+
+    /// Sine of the sun's height above the horizon: 1 at noon, -1 at midnight
+    pub fn sun_elevation(&self) -> f32 {
+        self.sun_angle().sin()
+    }
+
+    pub fn advance(&mut self, seconds: f32, day_length: f32) {
+        if day_length > 0.0 {
+            *self = Self::new(self.hours + seconds / day_length * 24.0);
+        }
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        match self.hours {
+            h if (5.0..9.0).contains(&h) => DayPhase::Dawn,
+            h if (9.0..17.0).contains(&h) => DayPhase::Noon,
+            h if (17.0..21.0).contains(&h) => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+}
+
+/// Parts of the day, [`DayPhaseChanged`] is triggered when the clock enters one
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayPhase {
+    /// 5:00 to 9:00
+    Dawn,
+    /// 9:00 to 17:00
+    Noon,
+    /// 17:00 to 21:00
+    Dusk,
+    /// 21:00 to 5:00
+    Night,
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Weather>().init_resource::<Weather>();
+}
+
+/// Weather states, only cycled while the sun cycle is [`SunCycle::Nimbus`]
+#[derive(Reflect, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Overcast,
+    Rain,
+    Fog,
+    Snow,
+}
+
+impl WeatherKind {
+    pub const ALL: [Self; 5] = [
+        Self::Clear,
+        Self::Overcast,
+        Self::Rain,
+        Self::Fog,
+        Self::Snow,
+    ];
+
+    /// Next kind in [`Self::ALL`], wrapping around
+    pub fn next(self) -> Self {
+        let i = Self::ALL
+            .iter()
+            .position(|k| *k == self)
+            .unwrap_or_default();
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Current weather and the transition into the next one
+#[derive(Resource, Reflect, Debug, Clone)]
+#[reflect(Resource)]
+pub struct Weather {
+    pub current: WeatherKind,
+    /// Weather faded into over the transition timer
+    pub next: Option<(WeatherKind, Timer)>,
+    /// Time left until the next weather change is picked
+    pub hold: Timer,
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Self {
+            current: WeatherKind::Clear,
+            next: None,
+            hold: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+}
+
+impl Weather {
+    /// Starts fading into `kind`, unless it is already the current or next weather
+    pub fn transition(&mut self, kind: WeatherKind, seconds: f32) {
+        let target = self.next.as_ref().map_or(self.current, |(next, _)| *next);
+        if target != kind {
+            self.next = Some((kind, Timer::from_seconds(seconds, TimerMode::Once)));
+        }
+    }
+
+    /// Weather that is more than halfway in, e.g. for footsteps
+    pub fn dominant(&self) -> WeatherKind {
+        match &self.next {
+            Some((next, timer)) if timer.fraction() > 0.5 => *next,
+            _ => self.current,
+        }
+    }
+
+    /// [`WeatherParams`] blended across the transition
+    pub fn params(&self, cfg: &WeatherConfig) -> WeatherParams {
+        let current = cfg.params(self.current);
+        match &self.next {
+            Some((next, timer)) => current.lerp(&cfg.params(*next), timer.fraction()),
+            None => current,
+        }
+    }
+}
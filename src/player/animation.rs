@@ -50,12 +50,15 @@ pub fn animating(
         &TnuaController,
         &mut Player,
         &mut TnuaAnimatingState<AnimationState>,
+        Has<MeleeAttack>,
+        Has<Dead>,
     )>,
     mut animation_player: Query<&mut AnimationPlayer>,
 ) {
     // An actual game should match the animation player and the controller. Here we cheat for
     // simplicity and use the only controller and only player.
-    let Ok((controller, mut player, mut animating_state)) = player_q.single_mut() else {
+    let Ok((controller, mut player, mut animating_state, attacking, dead)) = player_q.single_mut()
+    else {
         return;
     };
     let Ok(mut animation_player) = animation_player.single_mut() else {
@@ -67,6 +70,9 @@ pub fn animating(
     // so that we can later use that information to decide which animation to play.
     // First we look at the `action_name` to determine which action (if at all) the character is currently performing:
     let current_animation = match controller.action_name() {
+        // Death and melee swing are not tnua actions, so they take priority over whatever tnua is doing
+        _ if dead => AnimationState::Death,
+        _ if attacking => AnimationState::Attack,
         Some(TnuaBuiltinKnockback::NAME) => {
             let (_, knockback_state) = controller
                 .concrete_action::<TnuaBuiltinKnockback>()
@@ -266,6 +272,16 @@ pub fn animating(
                         animation_player.start(*index).set_speed(1.0);
                     }
                 }
+                AnimationState::Attack => {
+                    if let Some(index) = player.animations.get("Punch_Jab") {
+                        animation_player.start(*index).set_speed(1.5);
+                    }
+                }
+                AnimationState::Death => {
+                    if let Some(index) = player.animations.get("Death01") {
+                        animation_player.start(*index).set_speed(1.0);
+                    }
+                }
                 AnimationState::Climb(speed) => {
                     if let Some(index) = player.animations.get("Jump_Loop") {
                         animation_player.start(*index).set_speed(*speed).repeat();
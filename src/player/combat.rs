@@ -0,0 +1,116 @@
+//! Melee combat: [`Attack`] input turns into a timed swing with windup, active and recovery
+//! frames. During the active window a sensor hitbox is spawned in front of the attacker and every
+//! [`Damageable`] entity it overlaps receives a [`Hit`].
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_melee_attack.in_set(AppSystems::TickTimers),
+            melee_hits.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    )
+    .add_observer(handle_attack);
+}
+
+fn handle_attack(
+    on: Trigger<Started<Attack>>,
+    cfg: Res<Config>,
+    state: Res<GameState>,
+    attacking: Query<(), With<MeleeAttack>>,
+    mut commands: Commands,
+) {
+    let entity = on.target();
+    // one swing at a time, no buffering
+    if state.paused || attacking.contains(entity) {
+        return;
+    }
+
+    commands
+        .entity(entity)
+        .insert(MeleeAttack::new(cfg.player.melee.windup));
+}
+
+fn tick_melee_attack(
+    time: Res<Time>,
+    cfg: Res<Config>,
+    mut commands: Commands,
+    mut attackers: Query<(Entity, &mut MeleeAttack)>,
+) {
+    let melee = &cfg.player.melee;
+    for (entity, mut attack) in attackers.iter_mut() {
+        if !attack.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        match attack.phase {
+            AttackPhase::Windup => {
+                let hitbox = commands
+                    .spawn((
+                        Name::new("Melee Hitbox"),
+                        MeleeHitbox { owner: entity },
+                        Sensor,
+                        Collider::sphere(melee.radius),
+                        CollidingEntities::default(),
+                        Transform::from_xyz(0.0, 0.0, -melee.reach),
+                        ChildOf(entity),
+                    ))
+                    .id();
+                attack.hitbox = Some(hitbox);
+                attack.phase = AttackPhase::Active;
+                attack.timer = Timer::from_seconds(melee.active, TimerMode::Once);
+            }
+            AttackPhase::Active => {
+                if let Some(hitbox) = attack.hitbox.take() {
+                    commands.entity(hitbox).despawn();
+                }
+                attack.phase = AttackPhase::Recovery;
+                attack.timer = Timer::from_seconds(melee.recovery, TimerMode::Once);
+            }
+            AttackPhase::Recovery => {
+                commands.entity(entity).remove::<MeleeAttack>();
+            }
+        }
+    }
+}
+
+fn melee_hits(
+    cfg: Res<Config>,
+    hitboxes: Query<(&MeleeHitbox, &CollidingEntities)>,
+    colliders: Query<&ColliderOf>,
+    damageable: Query<(), With<Damageable>>,
+    mut attacks: Query<&mut MeleeAttack>,
+    mut commands: Commands,
+) {
+    for (hitbox, colliding) in hitboxes.iter() {
+        let Ok(mut attack) = attacks.get_mut(hitbox.owner) else {
+            continue;
+        };
+
+        for &collider in colliding.iter() {
+            // colliders spawned by skein are usually children of the damageable body
+            let body = colliders.get(collider).map(|c| c.body).unwrap_or(collider);
+            let Some(target) = [collider, body]
+                .into_iter()
+                .find(|e| damageable.contains(*e))
+            else {
+                continue;
+            };
+            if target == hitbox.owner || attack.hits.contains(&target) {
+                continue;
+            }
+
+            attack.hits.push(target);
+            info!("melee hit: {} -> {target}", hitbox.owner);
+            commands.trigger_targets(
+                Hit {
+                    attacker: hitbox.owner,
+                    damage: cfg.player.melee.damage,
+                },
+                target,
+            );
+        }
+    }
+}
@@ -1,65 +1,139 @@
 use super::*;
 use bevy_tnua::{
-    builtins::{TnuaBuiltinCrouch, TnuaBuiltinDash},
+    builtins::{TnuaBuiltinClimb, TnuaBuiltinCrouch, TnuaBuiltinDash, TnuaBuiltinWallSlide},
     control_helpers::TnuaSimpleAirActionsCounter,
 };
 
 pub fn plugin(app: &mut App) {
     app.add_systems(
         Update,
-        movement
+        (apply_locomotion, detect_wall, movement)
+            .chain()
             .in_set(TnuaUserControlsSystemSet)
             .run_if(in_state(Screen::Gameplay)),
     )
     .add_observer(handle_sprint_in)
     .add_observer(handle_sprint_out)
+    .add_observer(stop_toggled_sprint)
     .add_observer(handle_jump)
     .add_observer(handle_dash)
-    // .add_observer(handle_attack)
     .add_observer(crouch_in)
     .add_observer(crouch_out);
 }
 
+/// Casts the player's shape towards the input, or back into the wall it is already on,
+/// so [`movement`] can slide down or climb it
+#[allow(clippy::type_complexity)]
+fn detect_wall(
+    cfg: Res<Config>,
+    spatial: SpatialQuery,
+    navigate: Single<&Action<Navigate>>,
+    camera: Query<&Transform, With<SceneCamera>>,
+    sensors: Query<(), With<Sensor>>,
+    climbable: Query<(), With<Climbable>>,
+    bodies: Query<&ColliderOf>,
+    parents: Query<&ChildOf>,
+    mut players: Query<(Entity, &Transform, &TnuaController, &mut WallContact), With<Player>>,
+) -> Result {
+    let cam_transform = camera.single()?;
+    let input = cam_transform.movement_direction(**navigate.into_inner());
+    let hitbox = &cfg.player.hitbox;
+    let shape = Collider::sphere(hitbox.radius * 0.9);
+
+    for (entity, tf, controller, mut wall) in &mut players {
+        let on_wall = matches!(
+            controller.action_name(),
+            Some(TnuaBuiltinWallSlide::NAME | TnuaBuiltinClimb::NAME)
+        );
+        let direction = match wall.0 {
+            Some(hit) if on_wall => Dir3::new(-*hit.normal).ok(),
+            _ => Dir3::new(input).ok(),
+        };
+        let Some(direction) = direction else {
+            wall.0 = None;
+            continue;
+        };
+
+        let hit = spatial.cast_shape_predicate(
+            &shape,
+            tf.translation,
+            Quat::IDENTITY,
+            direction,
+            &ShapeCastConfig::from_max_distance(cfg.player.movement.wall_distance),
+            &SpatialQueryFilter::from_excluded_entities([entity]),
+            &|e| !sensors.contains(e),
+        );
+        wall.0 = hit.and_then(|hit| {
+            // floors and ceilings are not walls
+            let normal = Vec3::new(hit.normal1.x, 0.0, hit.normal1.z);
+            if hit.normal1.y.abs() > 0.5 {
+                return None;
+            }
+            // the tag may be on the collider, its body or any of their parents
+            let body = bodies.get(hit.entity).map_or(hit.entity, |c| c.body);
+            let climbable = [hit.entity, body]
+                .into_iter()
+                .flat_map(|e| std::iter::once(e).chain(parents.iter_ancestors(e)))
+                .any(|e| climbable.contains(e));
+            Some(WallHit {
+                entity: hit.entity,
+                point: hit.point1,
+                normal: Dir3::new(normal).ok()?,
+                climbable,
+            })
+        });
+    }
+
+    Ok(())
+}
+
 /// Tnua configuration is tricky to grasp from the get go, this is the best demo:
 /// <https://github.com/idanarye/bevy-tnua/blob/main/demos/src/character_control_systems/platformer_control_systems.rs>
 fn movement(
     cfg: Res<Config>,
     navigate: Single<&Action<Navigate>>,
-    crouch: Single<&Action<Crouch>>,
     camera: Query<&Transform, With<SceneCamera>>,
-    mut player_query: Query<(&mut Player, &mut TnuaController, &mut StepTimer)>,
+    mut player_query: Query<(
+        &Player,
+        &Locomotion,
+        &mut TnuaController,
+        &mut StepTimer,
+        &WallContact,
+    )>,
 ) -> Result {
-    let (navigate, crouch) = (*navigate.into_inner(), *crouch.into_inner());
-    info!("{}, {}", *navigate, *crouch);
-    for (player, mut controller, mut step_timer) in player_query.iter_mut() {
+    let navigate = *navigate.into_inner();
+    for (player, locomotion, mut controller, mut step_timer, wall) in player_query.iter_mut() {
         let cam_transform = camera.single()?;
         let direction = cam_transform.movement_direction(*navigate);
 
-        let float_height = 0.5;
+        let walk = &cfg.player.movement.walk;
         controller.basis(TnuaBuiltinWalk {
-            float_height,
-            cling_distance: float_height + 0.01, // Slightly higher than float_height for a bit of "give".
-            spring_strength: 500.0,              // Stronger spring for a more grounded feel.
-            spring_dampening: 1.0, // Slightly reduced dampening for a more responsive spring.
-            acceleration: 80.0,    // Increased acceleration for snappier movement starts and stops.
-            air_acceleration: 30.0, // Allow for some air control, but less than ground.
-            free_fall_extra_gravity: 70.0, // Slightly increased for a less floaty fall.
-            tilt_offset_angvel: 7.0, // Increased for a slightly faster righting response.
-            tilt_offset_angacl: 700.0, // Increased acceleration to reach the target righting speed.
-            turning_angvel: 12.0,  // Increased for more responsive turning.
+            float_height: walk.float_height,
+            cling_distance: walk.cling_distance,
+            spring_strength: walk.spring_strength,
+            spring_dampening: walk.spring_dampening,
+            acceleration: walk.acceleration,
+            air_acceleration: walk.air_acceleration,
+            free_fall_extra_gravity: walk.free_fall_extra_gravity,
+            tilt_offset_angvel: walk.tilt_offset_angvel,
+            tilt_offset_angacl: walk.tilt_offset_angacl,
+            turning_angvel: walk.turning_angvel,
             desired_velocity: direction * player.speed,
             desired_forward: Dir3::new(direction).ok(),
             ..Default::default()
         });
 
         // Check if crouch is currently active and apply TnuaBuiltinCrouch as an action
-        if *crouch {
+        if *locomotion == Locomotion::Crouch {
+            let tuning = &cfg.player.movement.crouch;
             controller.action(TnuaBuiltinCrouch {
-                float_offset: 0.0,
-                height_change_impulse_for_duration: 0.1,
-                height_change_impulse_limit: 80.0,
+                float_offset: tuning.float_offset,
+                height_change_impulse_for_duration: tuning.height_change_impulse_for_duration,
+                height_change_impulse_limit: tuning.height_change_impulse_limit,
                 uncancellable: false,
             });
+        } else if let Some(hit) = wall.0 {
+            wall_actions(&cfg, &mut controller, hit, direction, navigate.y);
         }
 
         // update step timer dynamically based on actual speed
@@ -83,61 +157,146 @@ fn movement(
     Ok(())
 }
 
+/// Climbs a [`Climbable`] wall the player pushes into or already hangs on, forward climbs up.
+/// Other walls are slid down while pushing into them in the air.
+fn wall_actions(
+    cfg: &Config,
+    controller: &mut TnuaController,
+    hit: WallHit,
+    direction: Vec3,
+    vertical: f32,
+) {
+    let movement = &cfg.player.movement;
+    let into_wall = -*hit.normal;
+    let pushing = direction.dot(into_wall) > 0.3;
+    let climbing = controller.action_name() == Some(TnuaBuiltinClimb::NAME);
+    let airborne = controller
+        .concrete_basis::<TnuaBuiltinWalk>()
+        .is_some_and(|(_, basis)| basis.standing_on_entity().is_none());
+
+    if hit.climbable && (pushing || climbing) {
+        // climbing down onto the ground lets go
+        if climbing && !airborne && vertical < 0.0 {
+            return;
+        }
+        controller.action(TnuaBuiltinClimb {
+            climbable_entity: Some(hit.entity),
+            anchor: hit.point,
+            desired_vec_to_anchor: into_wall * cfg.player.hitbox.radius,
+            desired_climb_velocity: Vec3::Y * vertical * movement.climb_speed,
+            desired_forward: Some(Dir3::new(into_wall).unwrap_or(Dir3::NEG_Z)),
+            initiation_direction: into_wall,
+            ..default()
+        });
+    } else if airborne && pushing {
+        controller.action(TnuaBuiltinWallSlide {
+            wall_entity: Some(hit.entity),
+            contact_point_with_wall: hit.point,
+            normal: hit.normal,
+            force_forward: Dir3::new(into_wall).ok(),
+            max_fall_speed: movement.wall_slide_speed,
+            maintain_distance: Some(cfg.player.hitbox.radius),
+            ..default()
+        });
+    }
+}
+
 fn handle_sprint_in(
     on: Trigger<Started<Sprint>>,
-    cfg: Res<Config>,
-    mut player_query: Query<&mut Player, With<PlayerCtx>>,
+    settings: Res<Settings>,
+    mut player_query: Query<(&mut Locomotion, &Stamina), With<PlayerCtx>>,
 ) -> Result {
-    let entity = on.target();
-    if let Ok(mut player) = player_query.get_mut(entity) {
-        if player.speed <= cfg.player.movement.speed {
-            player.speed *= cfg.player.movement.sprint_factor;
-        }
-        info!("Sprint started for entity: {entity}");
+    let (mut locomotion, stamina) = player_query.get_mut(on.target())?;
+    if settings.controls.sprint == ModifierMode::Toggle && *locomotion == Locomotion::Sprint {
+        *locomotion = Locomotion::Walk;
+    } else if stamina.exhausted {
+        info!("too exhausted to sprint");
+    } else {
+        *locomotion = Locomotion::Sprint;
     }
 
     Ok(())
 }
 
 fn handle_sprint_out(
+    on: Trigger<Completed<Sprint>>,
+    settings: Res<Settings>,
+    mut player_query: Query<&mut Locomotion, With<PlayerCtx>>,
+) -> Result {
+    let mut locomotion = player_query.get_mut(on.target())?;
+    if settings.controls.sprint == ModifierMode::Hold && *locomotion == Locomotion::Sprint {
+        *locomotion = Locomotion::Walk;
+    }
+
+    Ok(())
+}
+
+/// A toggled sprint ends once the player stops moving
+fn stop_toggled_sprint(
     on: Trigger<Completed<Navigate>>,
-    cfg: Res<Config>,
-    mut player_query: Query<&mut Player, With<PlayerCtx>>,
-) {
-    let entity = on.target();
-    match player_query.get_mut(entity) {
-        Ok(mut player) if player.speed > cfg.player.movement.speed => {
-            player.speed = cfg.player.movement.speed;
-        }
-        _ => {}
+    settings: Res<Settings>,
+    mut player_query: Query<&mut Locomotion, With<PlayerCtx>>,
+) -> Result {
+    let mut locomotion = player_query.get_mut(on.target())?;
+    if settings.controls.sprint == ModifierMode::Toggle && *locomotion == Locomotion::Sprint {
+        *locomotion = Locomotion::Walk;
     }
+
+    Ok(())
 }
 
 fn handle_jump(
     on: Trigger<Fired<Jump>>,
-    // cfg: Res<Config>,
+    cfg: Res<Config>,
     // time: Res<Time>,
     mut player_query: Query<
         (
             &mut TnuaController,
             &mut TnuaSimpleAirActionsCounter,
             &mut JumpTimer,
+            &WallContact,
+            &mut LinearVelocity,
         ),
         With<Player>,
     >,
 ) -> Result {
-    let (mut controller, mut air_counter, mut _jump_timer) = player_query.get_mut(on.target())?;
+    let (mut controller, mut air_counter, mut _jump_timer, wall, mut velocity) =
+        player_query.get_mut(on.target())?;
+
+    // jumping off a wall pushes away from it, the jump is kept alive while the button is held
+    let on_wall = matches!(
+        controller.action_name(),
+        Some(TnuaBuiltinWallSlide::NAME | TnuaBuiltinClimb::NAME | "walljump")
+    );
+    if let (true, Some(hit)) = (on_wall, wall.0) {
+        let movement = &cfg.player.movement;
+        if controller.action_name() != Some("walljump") {
+            velocity.0 += *hit.normal * movement.wall_jump_push;
+        }
+        controller.named_action(
+            "walljump",
+            TnuaBuiltinJump {
+                height: movement.wall_jump_height,
+                allow_in_air: true,
+                force_forward: Some(hit.normal),
+                disable_force_forward_after_peak: true,
+                ..Default::default()
+            },
+        );
+        return Ok(());
+    }
 
     // if jump_timer.tick(time.delta()).just_finished() {
     air_counter.update(controller.as_mut()); // Update air counter
+    let jump = &cfg.player.movement.jump;
     controller.action(TnuaBuiltinJump {
-        height: 3.5,
-        takeoff_extra_gravity: 50.0, // Increased for a snappier, more immediate lift-off.
-        fall_extra_gravity: 40.0,    // To make falling feel more impactful and less floaty.
-        shorten_extra_gravity: 80.0, // Increased to allow for very short hops when tapping the jump button.
-        peak_prevention_at_upward_velocity: 0.5, // Slightly lower to start applying peak prevention sooner.
-        peak_prevention_extra_gravity: 30.0, // Increased to reduce "floatiness" at the jump's apex.
-        reschedule_cooldown: Some(0.1), // Allows for a slight "jump buffering" if the button is pressed just before landing.
+        height: jump.height,
+        takeoff_extra_gravity: jump.takeoff_extra_gravity,
+        fall_extra_gravity: jump.fall_extra_gravity,
+        shorten_extra_gravity: jump.shorten_extra_gravity,
+        peak_prevention_at_upward_velocity: jump.peak_prevention_at_upward_velocity,
+        peak_prevention_extra_gravity: jump.peak_prevention_extra_gravity,
+        reschedule_cooldown: Some(jump.reschedule_cooldown),
         disable_force_forward_after_peak: true,
         allow_in_air: true,
         ..Default::default()
@@ -152,62 +311,92 @@ fn handle_dash(
     cfg: Res<Config>,
     navigate: Single<&Action<Navigate>>,
     camera: Query<&Transform, With<SceneCamera>>,
-    mut player_query: Query<(&mut TnuaController, &TnuaSimpleAirActionsCounter)>,
+    mut player_query: Query<(
+        &mut TnuaController,
+        &TnuaSimpleAirActionsCounter,
+        &mut Stamina,
+    )>,
 ) -> Result {
-    let (mut controller, air_counter) = player_query.get_mut(on.target())?;
+    let (mut controller, air_counter, mut stamina) = player_query.get_mut(on.target())?;
+    let allow_in_air = air_counter.air_count_for(TnuaBuiltinDash::NAME)
+        <= cfg.player.movement.actions_in_air.into();
+    let airborne = controller
+        .concrete_basis::<TnuaBuiltinWalk>()
+        .is_some_and(|(_, basis)| basis.standing_on_entity().is_none());
+    if airborne && !allow_in_air {
+        return Ok(());
+    }
+    let cost = cfg.player.stamina.dash_cost;
+    if !stamina.can_spend(cost) {
+        info!("not enough stamina to dash");
+        return Ok(());
+    }
+    stamina.spend(cost);
+
     let cam_transform = camera.single()?;
     let navigate = **navigate.into_inner();
     let direction = cam_transform.movement_direction(navigate);
 
     controller.action(TnuaBuiltinDash {
-        speed: 50.,
+        speed: cfg.player.movement.dash_speed,
         displacement: direction * cfg.player.movement.dash_distance,
         desired_forward: Dir3::new(direction).ok(),
-        allow_in_air: air_counter.air_count_for(TnuaBuiltinDash::NAME)
-            <= cfg.player.movement.actions_in_air.into(),
+        allow_in_air,
         ..Default::default()
     });
 
     Ok(())
 }
 
-// fn handle_attack(on: Trigger<Started<Attack>>, mut commands: Commands) {
-//     let entity = on.target();
-//     // TODO: Hit
-// }
-
 pub fn crouch_in(
     on: Trigger<Started<Crouch>>,
-    cfg: Res<Config>,
-    mut player: Query<&mut Player, With<PlayerCtx>>,
-    mut tnua: Query<(&mut TnuaAvian3dSensorShape, &mut Collider), With<Player>>,
+    settings: Res<Settings>,
+    mut player_query: Query<&mut Locomotion, With<PlayerCtx>>,
 ) -> Result {
-    let (mut avian_sensor, mut collider) = tnua.single_mut()?;
-    let mut player = player.get_mut(on.target())?;
-
-    collider.set_scale(Vec3::new(1.0, 0.5, 1.0), 4);
-    avian_sensor.0.set_scale(Vec3::new(1.0, 0.5, 1.0), 4);
-    player.speed *= cfg.player.movement.crouch_factor;
+    let mut locomotion = player_query.get_mut(on.target())?;
+    *locomotion = match (settings.controls.crouch, *locomotion) {
+        (ModifierMode::Toggle, Locomotion::Crouch) => Locomotion::Walk,
+        _ => Locomotion::Crouch,
+    };
 
     Ok(())
 }
 
-#[allow(clippy::type_complexity)]
 pub fn crouch_out(
     on: Trigger<Completed<Crouch>>,
-    cfg: Res<Config>,
-    mut player: Query<&mut Player, With<PlayerCtx>>,
-    mut tnua: Query<
-        (&mut TnuaAvian3dSensorShape, &mut Collider),
-        (With<Player>, Without<SceneCamera>),
-    >,
+    settings: Res<Settings>,
+    mut player_query: Query<&mut Locomotion, With<PlayerCtx>>,
 ) -> Result {
-    let (mut avian_sensor, mut collider) = tnua.get_mut(on.target())?;
-    let mut player = player.get_mut(on.target())?;
-
-    collider.set_scale(Vec3::ONE, 4);
-    avian_sensor.0.set_scale(Vec3::ONE, 4);
-    player.speed = cfg.player.movement.speed;
+    let mut locomotion = player_query.get_mut(on.target())?;
+    if settings.controls.crouch == ModifierMode::Hold && *locomotion == Locomotion::Crouch {
+        *locomotion = Locomotion::Walk;
+    }
 
     Ok(())
 }
+
+/// Sets [`Player::speed`] from the [`Locomotion`] and squashes the collider while crouching.
+/// Also reruns when [`Config`] is hot reloaded so speed factors apply right away
+fn apply_locomotion(
+    cfg: Res<Config>,
+    mut players: Query<(
+        &mut Player,
+        Ref<Locomotion>,
+        &mut Collider,
+        &mut TnuaAvian3dSensorShape,
+    )>,
+) {
+    for (mut player, locomotion, mut collider, mut sensor) in &mut players {
+        if !locomotion.is_changed() && !cfg.is_changed() {
+            continue;
+        }
+        player.speed = locomotion.speed(&cfg.player.movement);
+        let scale = if *locomotion == Locomotion::Crouch {
+            Vec3::new(1.0, 0.5, 1.0)
+        } else {
+            Vec3::ONE
+        };
+        collider.set_scale(scale, 4);
+        sensor.0.set_scale(scale, 4);
+    }
+}
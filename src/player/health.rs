@@ -0,0 +1,248 @@
+//! Health, damage and the death → fade → respawn flow.
+//!
+//! Any entity with [`Health`] reacts to [`DamageEvent`]s: melee [`Hit`]s and touching a collider
+//! with [`Damage`]. The player gets knocked back with tnua and on zero HP fades out and respawns
+//! at [`PlayerConfig::spawn_pos`], everything else is simply despawned.
+use super::*;
+use bevy_tnua::builtins::TnuaBuiltinKnockback;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<DeathFade>()
+        .add_systems(
+            Update,
+            (
+                (tick_invulnerability, tick_respawn, tick_death_fade)
+                    .in_set(AppSystems::TickTimers),
+                (contact_damage, respawn, apply_death_fade).in_set(AppSystems::Update),
+            )
+                .run_if(in_state(Screen::Gameplay)),
+        )
+        .add_observer(hit_to_damage)
+        .add_observer(take_damage)
+        .add_observer(die);
+}
+
+fn hit_to_damage(on: Trigger<Hit>, mut commands: Commands) {
+    let hit = on.event();
+    commands.trigger_targets(
+        DamageEvent {
+            source: Some(hit.attacker),
+            amount: hit.damage,
+        },
+        on.target(),
+    );
+}
+
+fn contact_damage(
+    hazards: Query<&Damage>,
+    colliders: Query<&ColliderOf>,
+    victims: Query<
+        (Entity, &CollidingEntities),
+        (With<Health>, Without<Invulnerable>, Without<Dead>),
+    >,
+    mut commands: Commands,
+) {
+    for (victim, colliding) in victims.iter() {
+        for &collider in colliding.iter() {
+            let body = colliders.get(collider).map(|c| c.body).unwrap_or(collider);
+            let Some(damage) = hazards.get(collider).or(hazards.get(body)).ok() else {
+                continue;
+            };
+            commands.trigger_targets(
+                DamageEvent {
+                    source: Some(body),
+                    amount: damage.0,
+                },
+                victim,
+            );
+            // one hazard per frame is enough, invulnerability takes care of the rest
+            break;
+        }
+    }
+}
+
+fn take_damage(
+    on: Trigger<DamageEvent>,
+    cfg: Res<Config>,
+    transforms: Query<&GlobalTransform>,
+    players: Query<(), With<Player>>,
+    mut victims: Query<
+        (
+            &mut Health,
+            Option<&mut TnuaController>,
+            Option<&MeleeAttack>,
+        ),
+        (Without<Invulnerable>, Without<Dead>),
+    >,
+    mut commands: Commands,
+) {
+    let target = on.target();
+    let Ok((mut health, controller, attack)) = victims.get_mut(target) else {
+        return;
+    };
+    let event = on.event();
+    health.current = (health.current - event.amount).max(0.0);
+    info!(
+        "{target} took {} damage, health: {}/{}",
+        event.amount, health.current, health.max
+    );
+
+    // getting hit interrupts the swing
+    if let Some(attack) = attack {
+        if let Some(hitbox) = attack.hitbox {
+            commands.entity(hitbox).despawn();
+        }
+        commands.entity(target).remove::<MeleeAttack>();
+    }
+
+    if health.current <= 0.0 {
+        if players.contains(target) {
+            commands.trigger_targets(Died, target);
+        } else {
+            commands.entity(target).despawn();
+        }
+        return;
+    }
+
+    commands
+        .entity(target)
+        .insert(Invulnerable(Timer::from_seconds(
+            cfg.timers.invulnerability,
+            TimerMode::Once,
+        )));
+
+    let Some(mut controller) = controller else {
+        return;
+    };
+    // push away from the source of damage, or just up if there is none
+    let direction = event
+        .source
+        .and_then(|source| transforms.get(source).ok())
+        .zip(transforms.get(target).ok())
+        .map(|(source, target)| (target.translation() - source.translation()).with_y(0.0))
+        .unwrap_or_default()
+        .normalize_or_zero();
+    controller.action(TnuaBuiltinKnockback {
+        shove: (direction + Vec3::Y * 0.5) * cfg.player.knockback,
+        force_forward: Dir3::new(-direction).ok(),
+        ..Default::default()
+    });
+}
+
+fn die(
+    on: Trigger<Died>,
+    cfg: Res<Config>,
+    mut player: Query<&mut TnuaController, With<Player>>,
+    mut commands: Commands,
+) {
+    let entity = on.target();
+    info!("player {entity} died");
+    if let Ok(mut controller) = player.get_mut(entity) {
+        // input is not fed while dead, so stop walking in the last direction
+        controller.neutralize_basis();
+    }
+
+    commands.entity(entity).remove::<PlayerCtx>().insert((
+        Dead,
+        RespawnTimer(Timer::from_seconds(cfg.timers.respawn, TimerMode::Once)),
+    ));
+    commands.spawn((
+        StateScoped(Screen::Gameplay),
+        ui_root("Death Fade"),
+        BackgroundColor(Color::BLACK.with_alpha(0.0)),
+        GlobalZIndex(1),
+        DeathFade {
+            duration: cfg.timers.respawn,
+            t: 0.0,
+        },
+    ));
+}
+
+fn respawn(
+    cfg: Res<Config>,
+    mut commands: Commands,
+    mut player: Query<(
+        Entity,
+        &RespawnTimer,
+        &mut Locomotion,
+        &mut Stamina,
+        &mut Health,
+        &mut Transform,
+        &mut LinearVelocity,
+    )>,
+) {
+    for (entity, timer, mut locomotion, mut stamina, mut health, mut tf, mut velocity) in
+        player.iter_mut()
+    {
+        if !timer.just_finished() {
+            continue;
+        }
+
+        tf.translation = Vec3::from(cfg.player.spawn_pos);
+        *velocity = LinearVelocity::ZERO;
+        health.current = health.max;
+        stamina.refill();
+        // modifiers never get released without the input context, so stand up here
+        *locomotion = Locomotion::Walk;
+
+        commands
+            .entity(entity)
+            .remove::<(Dead, RespawnTimer)>()
+            .insert(PlayerCtx);
+        info!("player {entity} respawned");
+    }
+}
+
+fn tick_invulnerability(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut timers: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut timer) in timers.iter_mut() {
+        if timer.tick(time.delta()).just_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+fn tick_respawn(time: Res<Time>, mut timers: Query<&mut RespawnTimer>) {
+    for mut timer in timers.iter_mut() {
+        timer.tick(time.delta());
+    }
+}
+
+/// Full screen overlay that fades to black for `duration` and then back for the same time
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DeathFade {
+    /// Fade duration in seconds.
+    duration: f32,
+    /// Current progress in seconds, between 0 and twice the [`Self::duration`].
+    t: f32,
+}
+
+impl DeathFade {
+    fn alpha(&self) -> f32 {
+        // Triangle-shaped graph with the peak at the moment of respawn
+        (1.0 - (self.t / self.duration - 1.0).abs()).clamp(0.0, 1.0)
+    }
+}
+
+fn tick_death_fade(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut fades: Query<(Entity, &mut DeathFade)>,
+) {
+    for (entity, mut fade) in fades.iter_mut() {
+        fade.t += time.delta_secs();
+        if fade.t >= 2.0 * fade.duration {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn apply_death_fade(mut fades: Query<(&DeathFade, &mut BackgroundColor)>) {
+    for (fade, mut background) in fades.iter_mut() {
+        background.0.set_alpha(fade.alpha());
+    }
+}
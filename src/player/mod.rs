@@ -12,8 +12,11 @@ use bevy_top_down_camera::*;
 use std::{f32::consts::PI, time::Duration};
 
 mod animation;
+mod combat;
 mod control;
+mod health;
 mod sound;
+mod stamina;
 
 pub use animation::*;
 
@@ -25,8 +28,11 @@ pub fn plugin(app: &mut App) {
     app.add_plugins((
         TnuaControllerPlugin::new(FixedUpdate),
         TnuaAvian3dPlugin::new(FixedUpdate),
+        combat::plugin,
         control::plugin,
+        health::plugin,
         sound::plugin,
+        stamina::plugin,
     ));
 
     #[cfg(feature = "third_person")]
@@ -96,6 +102,7 @@ pub fn spawn_player(
                 LockedAxes::ROTATION_LOCKED.unlock_rotation_y(),
                 TnuaAnimatingState::<AnimationState>::default(),
                 TnuaSimpleAirActionsCounter::default(),
+                WallContact::default(),
                 // A sensor shape is not strictly necessary, but without it we'll get weird results.
                 TnuaAvian3dSensorShape(collider.clone()),
             ),
@@ -104,9 +111,16 @@ pub fn spawn_player(
                 collider,
                 RigidBody::Dynamic,
                 Friction::ZERO.with_combine_rule(CoefficientCombine::Multiply),
+                // for contact damage
+                CollidingEntities::default(),
+                // mood zones can be made of any colliders, the player reports touching them
+                CollisionEventsEnabled,
             ),
             // other player related components
             (
+                Health::new(cfg.player.health),
+                Locomotion::default(),
+                Stamina::new(cfg.player.stamina.max),
                 JumpTimer(Timer::from_seconds(cfg.timers.jump, TimerMode::Repeating)),
                 StepTimer(Timer::from_seconds(cfg.timers.step, TimerMode::Repeating)),
                 InheritedVisibility::default(), // silence the warning because of adding SceneRoot as a child
@@ -9,15 +9,19 @@ pub fn plugin(app: &mut App) {
         .add_observer(jump_sound);
 }
 
+/// Steps sound like the [`SurfaceMaterial`] under the player, snow covers every surface.
+/// Crouching steps are quieter, sprinting ones louder and faster.
 #[allow(clippy::too_many_arguments)]
 fn movement_sound(
     on: Trigger<Fired<Navigate>>,
+    cfg: Res<Config>,
     time: Res<Time>,
     state: Res<GameState>,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
-    tnua: Query<&TnuaController, With<Player>>,
-    crouch: Single<&Action<Crouch>>,
+    weather: Res<Weather>,
+    samples: Res<StepSamples>,
+    tnua: Query<(&TnuaController, &Locomotion), With<Player>>,
+    surfaces: Query<&SurfaceMaterial>,
+    parents: Query<&ChildOf>,
     mut cmds: Commands,
     mut step_timer: Query<&mut StepTimer, With<Player>>,
 ) -> Result {
@@ -25,25 +29,50 @@ fn movement_sound(
         return Ok(());
     }
 
-    let controller = tnua.get(on.target())?;
+    let (controller, locomotion) = tnua.get(on.target())?;
     let mut step_timer = step_timer.get_mut(on.target())?;
 
     let Some((_, basis)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
         return Ok(());
     };
+    let Some(ground) = basis.standing_on_entity() else {
+        return Ok(());
+    };
 
     // WALK SOUND
-    if step_timer.tick(time.delta()).just_finished() && basis.standing_on_entity().is_some() {
-        let mut rng = thread_rng();
-        let i = rng.gen_range(0..sources.steps.len());
-        let crouch = ***crouch;
-        let handle = if crouch {
-            // TODO: select crouch steps
-            sources.steps[i].clone()
+    if step_timer.tick(time.delta()).just_finished() {
+        let surface = if weather.dominant() == WeatherKind::Snow {
+            SurfaceMaterial::Snow
         } else {
-            sources.steps[i].clone()
+            std::iter::once(ground)
+                .chain(parents.iter_ancestors(ground))
+                .find_map(|e| surfaces.get(e).ok())
+                .copied()
+                .unwrap_or_default()
         };
-        cmds.spawn((Sfx, SamplePlayer::new(handle).with_volume(settings.sfx())));
+        let steps = samples.surface(&cfg.footsteps, surface);
+        if steps.is_empty() {
+            return Ok(());
+        }
+        let handle = steps[thread_rng().gen_range(0..steps.len())].clone();
+
+        // 1 at run speed, up to the sprint factor when sprinting
+        let movement = &cfg.player.movement;
+        let gait = (basis.running_velocity.length() / movement.speed)
+            .clamp(0.5, movement.sprint_factor.max(1.0));
+        let (volume, pitch) = if *locomotion == Locomotion::Crouch {
+            (0.4, 0.9)
+        } else {
+            (gait, 0.9 + 0.1 * gait as f64)
+        };
+        let volume = surface.volume() * volume;
+        let pitch = surface.pitch() * pitch;
+
+        cmds.spawn((
+            Sfx,
+            SamplePlayer::new(handle).with_volume(Volume::Linear(volume)),
+            PitchRange(pitch * 0.95..pitch * 1.05),
+        ));
     }
 
     Ok(())
@@ -52,7 +81,6 @@ fn movement_sound(
 fn jump_sound(
     _: Trigger<Started<Jump>>,
     state: Res<GameState>,
-    settings: Res<Settings>,
     sources: ResMut<AudioSources>,
     // jump_timer: Query<&JumpTimer, With<Player>>,
     mut cmds: Commands,
@@ -66,7 +94,7 @@ fn jump_sound(
     let mut rng = thread_rng();
     let i = rng.gen_range(0..sources.steps.len());
     let handle = sources.steps[i].clone();
-    cmds.spawn((Sfx, SamplePlayer::new(handle).with_volume(settings.sfx())));
+    cmds.spawn((Sfx, SamplePlayer::new(handle)));
     // }
 
     Ok(())
@@ -75,7 +103,6 @@ fn jump_sound(
 fn dash_sound(
     _: Trigger<Started<Dash>>,
     state: Res<GameState>,
-    settings: Res<Settings>,
     sources: ResMut<AudioSources>,
     // jump_timer: Query<&JumpTimer, With<Player>>,
     mut cmds: Commands,
@@ -89,7 +116,7 @@ fn dash_sound(
     let mut rng = thread_rng();
     let i = rng.gen_range(0..sources.steps.len());
     let handle = sources.steps[i].clone();
-    cmds.spawn((Sfx, SamplePlayer::new(handle).with_volume(settings.sfx())));
+    cmds.spawn((Sfx, SamplePlayer::new(handle)));
     // }
 
     Ok(())
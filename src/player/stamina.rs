@@ -0,0 +1,43 @@
+//! [`Stamina`] drain while sprinting, regeneration after a delay and exhaustion.
+//! Dash spends its chunk directly in `handle_dash`.
+use super::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        update_stamina
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+fn update_stamina(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    mut players: Query<(&mut Locomotion, &mut Stamina, &TnuaController)>,
+) {
+    let stamina_cfg = &cfg.player.stamina;
+    for (mut locomotion, mut stamina, controller) in &mut players {
+        let moving = controller
+            .concrete_basis::<TnuaBuiltinWalk>()
+            .is_some_and(|(_, basis)| basis.running_velocity.length() > IDLE_TO_RUN_TRESHOLD);
+
+        if *locomotion == Locomotion::Sprint && moving {
+            stamina.spend(stamina_cfg.sprint_drain * time.delta_secs());
+            if stamina.exhausted {
+                info!("out of stamina, back to walking");
+                *locomotion = Locomotion::Walk;
+            }
+        } else {
+            stamina.since_spent += time.delta_secs();
+            if stamina.since_spent < stamina_cfg.regen_delay {
+                continue;
+            }
+            stamina.current =
+                (stamina.current + stamina_cfg.regen * time.delta_secs()).min(stamina.max);
+            if stamina.exhausted && stamina.fraction() >= stamina_cfg.recover {
+                stamina.exhausted = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Starts the [`AudioEmitter`]s placed in the scene
+use super::*;
+use bevy_seedling::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_observer(play_emitter);
+}
+
+/// Spawns the sample player as a child, so it follows the emitter and goes away with it.
+/// The ambience setting lives on the [`Ambience`] pool, the player only carries the emitter's own volume
+fn play_emitter(
+    on: Trigger<OnAdd, AudioEmitter>,
+    server: Res<AssetServer>,
+    emitters: Query<&AudioEmitter>,
+    mut commands: Commands,
+) -> Result {
+    let entity = on.target();
+    let emitter = emitters.get(entity)?;
+    if emitter.sample.is_empty() {
+        warn!("audio emitter {entity} has no sample");
+        return Ok(());
+    }
+
+    let volume = Volume::Linear(emitter.volume);
+    let mut player = SamplePlayer::new(server.load(&emitter.sample)).with_volume(volume);
+    if emitter.looping {
+        player = player.looping();
+    }
+    commands.spawn((
+        Name::new(format!("Audio Emitter: {}", emitter.sample)),
+        Ambience,
+        player,
+        Transform::default(),
+        ChildOf(entity),
+    ));
+    info!("playing audio emitter: {}", emitter.sample);
+
+    Ok(())
+}
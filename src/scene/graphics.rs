@@ -0,0 +1,168 @@
+//! Applies [`Graphics`] settings to the [`SceneCamera`] and the [`Sun`] and [`Moon`] lights
+use super::*;
+use bevy::{
+    core_pipeline::{bloom::Bloom, smaa::Smaa},
+    pbr::{CascadeShadowConfig, CascadeShadowConfigBuilder},
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    },
+    window::{PrimaryWindow, WindowRef, WindowResized},
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            apply_graphics,
+            apply_render_scale.run_if(resource_changed::<Settings>.or(on_event::<WindowResized>)),
+        )
+            .run_if(resource_exists::<Config>),
+    );
+}
+
+/// Shows the downscaled scene behind the UI, see [`Graphics::render_scale`]
+#[derive(Component)]
+struct RenderScaleView;
+
+/// Draws [`RenderScaleView`] and the UI at full window resolution
+#[derive(Component)]
+struct RenderScaleCamera;
+
+#[allow(clippy::type_complexity)]
+fn apply_graphics(
+    cfg: Res<Config>,
+    settings: Res<Settings>,
+    mut applied: Local<Option<Graphics>>,
+    new_lights: Query<(), Or<(Added<Sun>, Added<Moon>)>>,
+    camera: Query<Entity, With<SceneCamera>>,
+    mut lights: Query<&mut CascadeShadowConfig, Or<(With<Sun>, With<Moon>)>>,
+    mut commands: Commands,
+) {
+    let graphics = &settings.graphics;
+    if applied.as_ref() == Some(graphics) && new_lights.is_empty() {
+        return;
+    }
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+
+    let cascades = CascadeShadowConfigBuilder {
+        num_cascades: graphics.shadows.shadow_cascades(),
+        first_cascade_far_bound: 0.3,
+        maximum_distance: graphics.shadows.shadow_distance(),
+        ..default()
+    }
+    .build();
+    for mut config in &mut lights {
+        *config = cascades.clone();
+    }
+
+    let mut camera = commands.entity(camera);
+    camera.insert(graphics.atmosphere.atmosphere());
+    match graphics.antialiasing {
+        Antialiasing::Off => camera.insert(Msaa::Off).remove::<Smaa>(),
+        Antialiasing::Msaa => camera.insert(Msaa::Sample4).remove::<Smaa>(),
+        Antialiasing::Smaa => camera.insert((Msaa::Off, Smaa::default())),
+    };
+    if graphics.bloom {
+        camera.insert(Bloom::NATURAL);
+    } else {
+        camera.remove::<Bloom>();
+    }
+    if graphics.fog && cfg.physics.distance_fog {
+        camera.insert(distance_fog(&cfg));
+    } else {
+        camera.remove::<DistanceFog>();
+    }
+
+    info!("applied {} graphics preset", graphics.preset);
+    *applied = Some(graphics.clone());
+}
+
+/// Below full scale the scene camera renders into a smaller image, which a second camera
+/// stretches over the window under the UI
+#[allow(clippy::type_complexity)]
+fn apply_render_scale(
+    settings: Res<Settings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut scene_camera: Query<(Entity, &mut Camera), With<SceneCamera>>,
+    upscale: Query<Entity, With<RenderScaleCamera>>,
+    views: Query<(Entity, &ImageNode), With<RenderScaleView>>,
+    mut images: ResMut<Assets<Image>>,
+    mut commands: Commands,
+) {
+    let (Ok(window), Ok((camera_entity, mut camera))) =
+        (window.single(), scene_camera.single_mut())
+    else {
+        return;
+    };
+    let scale = settings.graphics.render_scale;
+
+    if scale >= 1.0 {
+        if matches!(camera.target, RenderTarget::Image(_)) {
+            camera.target = RenderTarget::Window(WindowRef::Primary);
+            commands.entity(camera_entity).insert(IsDefaultUiCamera);
+            for (entity, _) in &views {
+                commands.entity(entity).despawn();
+            }
+            for entity in &upscale {
+                commands.entity(entity).despawn();
+            }
+        }
+        return;
+    }
+
+    let size = (window.physical_size().as_vec2() * scale)
+        .as_uvec2()
+        .max(UVec2::ONE);
+    let extent = Extent3d {
+        width: size.x,
+        height: size.y,
+        ..default()
+    };
+    if let Ok((_, view)) = views.single() {
+        if let Some(image) = images.get_mut(&view.image) {
+            image.resize(extent);
+        }
+        return;
+    }
+
+    let mut image = Image::new_fill(
+        extent,
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    camera.target = RenderTarget::Image(image.clone().into());
+    commands.entity(camera_entity).remove::<IsDefaultUiCamera>();
+    commands.spawn((
+        Name::new("Render Scale Camera"),
+        RenderScaleCamera,
+        IsDefaultUiCamera,
+        Camera2d,
+        Camera {
+            order: 1,
+            ..default()
+        },
+    ));
+    commands.spawn((
+        Name::new("Render Scale View"),
+        RenderScaleView,
+        ImageNode::new(image),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        GlobalZIndex(i32::MIN),
+        Pickable::IGNORE,
+    ));
+}
@@ -2,7 +2,10 @@ use crate::*;
 use avian3d::prelude::*;
 use bevy_skein::SkeinPlugin;
 
+mod emitter;
+mod graphics;
 mod skybox;
+mod weather;
 
 pub use skybox::*;
 
@@ -14,6 +17,9 @@ pub fn plugin(app: &mut App) {
         SkeinPlugin::default(),
         bevy_fix_gltf_coordinate_system::FixGltfCoordinateSystemPlugin,
         skybox::plugin,
+        emitter::plugin,
+        graphics::plugin,
+        weather::plugin,
     ))
     .add_systems(OnEnter(Screen::Title), setup);
 }
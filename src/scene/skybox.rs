@@ -1,33 +1,40 @@
 use super::*;
 use bevy::{
-    core_pipeline::{
-        bloom::Bloom,
-        tonemapping::{DebandDither, Tonemapping},
-    },
-    pbr::{Atmosphere, AtmosphereSettings, CascadeShadowConfigBuilder, light_consts::lux},
+    core_pipeline::tonemapping::{DebandDither, Tonemapping},
+    pbr::{Atmosphere, light_consts::lux},
     render::camera::Exposure,
 };
+use std::f32::consts::PI;
+
+/// Brighter exposure at night so the moonlit scene stays readable
+const NIGHT_EXPOSURE: Exposure = Exposure { ev100: 9.0 };
+const DAY_AMBIENT: f32 = 500.0;
+const NIGHT_AMBIENT: f32 = 80.0;
 
 pub fn plugin(app: &mut App) {
-    app.add_systems(Update, sun_cycle.run_if(in_state(Screen::Gameplay)));
+    app.add_systems(OnEnter(Screen::Gameplay), reset_clock)
+        .add_systems(
+            Update,
+            (
+                (advance_clock, trigger_day_phase)
+                    .chain()
+                    .run_if(in_state(Screen::Gameplay)),
+                sun_cycle.run_if(resource_exists::<AmbientLight>.and(resource_exists::<Config>)),
+            )
+                .chain(),
+        );
 }
 
 /// Mainly this example:
 /// <https://bevyengine.org/examples/3d-rendering/atmosphere/>
+///
+/// Shadows, atmosphere quality, anti-aliasing, bloom and fog come from [`Graphics`]
 pub fn add_skybox_to_camera(
-    cfg: Res<Config>,
     mut commands: Commands,
     mut camera: Query<Entity, With<SceneCamera>>,
 ) -> Result {
     let camera = camera.single_mut()?;
 
-    let cascade_shadow_config = CascadeShadowConfigBuilder {
-        first_cascade_far_bound: 0.3,
-        maximum_distance: cfg.physics.shadow_distance,
-        ..default()
-    }
-    .build();
-
     commands.spawn((
         StateScoped(Screen::Gameplay),
         Sun,
@@ -38,7 +45,6 @@ pub fn add_skybox_to_camera(
             ..Default::default()
         },
         Transform::from_translation(Vec3::new(0.0, 0.0, 200.0)),
-        cascade_shadow_config.clone(),
     ));
 
     commands.spawn((
@@ -51,7 +57,6 @@ pub fn add_skybox_to_camera(
             ..Default::default()
         },
         Transform::from_translation(Vec3::new(0.0, 10.0, -200.0)),
-        cascade_shadow_config,
     ));
 
     // Lighting
@@ -59,40 +64,15 @@ pub fn add_skybox_to_camera(
         // This is the component that enables atmospheric scattering for a camera
         // TODO: manipulate ground_albedo depending on the angle of the sun
         Atmosphere::EARTH,
-        // The scene is in units of 10km, so we need to scale up the
-        // aerial view lut distance and set the scene scale accordingly.
-        // Most usages of this feature will not need to adjust this.
-        AtmosphereSettings {
-            scene_units_to_m: 1.0,
-            aerial_view_lut_max_distance: 40_000.0, //  40 km for a vast scene
-
-            // Higher resolution LUTs for smoother gradients and details
-            transmittance_lut_size: UVec2::new(512, 256), // Double resolution for smoother light transmission
-            sky_view_lut_size: UVec2::new(800, 400),      // Higher resolution for sky appearance
-            aerial_view_lut_size: UVec3::new(64, 64, 64), // More detailed aerial perspective
-
-            // Increased sample counts for better accuracy and less artifacts
-            transmittance_lut_samples: 60, // More samples for light transmission accuracy
-            multiscattering_lut_dirs: 128, // Double directions for multiscattering
-            multiscattering_lut_samples: 30, // More samples for multiscattering accuracy
-            sky_view_lut_samples: 24,      // More samples for sky appearance
-            aerial_view_lut_samples: 15,   // More samples for aerial view depth
-            ..Default::default()
-        },
         Tonemapping::BlenderFilmic,
         Exposure::OVERCAST,
-        Bloom::NATURAL,
         DebandDither::Enabled, // Bloom causes gradients which cause banding
     ));
 
-    if cfg.physics.distance_fog {
-        commands.entity(camera).insert(distance_fog(cfg));
-    }
-
     Ok(())
 }
 
-pub fn distance_fog(cfg: Res<Config>) -> impl Bundle {
+pub fn distance_fog(cfg: &Config) -> impl Bundle {
     DistanceFog {
         color: Color::srgba(0.35, 0.48, 0.66, 1.0),
         directional_light_color: Color::srgba(1.0, 0.95, 0.85, 0.5),
@@ -106,18 +86,74 @@ pub fn distance_fog(cfg: Res<Config>) -> impl Bundle {
     }
 }
 
+fn reset_clock(cfg: Res<Config>, mut commands: Commands) {
+    commands.insert_resource(TimeOfDay::new(cfg.day_cycle.start_hour));
+}
+
+fn advance_clock(cfg: Res<Config>, time: Res<Time>, mut clock: ResMut<TimeOfDay>) {
+    clock.advance(time.delta_secs(), cfg.day_cycle.length);
+}
+
+fn trigger_day_phase(
+    clock: Res<TimeOfDay>,
+    mut last: Local<Option<DayPhase>>,
+    mut commands: Commands,
+) {
+    let phase = clock.phase();
+    if *last != Some(phase) {
+        info!("day phase: {phase:?} at {:.1}h", clock.hours);
+        commands.trigger(DayPhaseChanged(phase));
+        *last = Some(phase);
+    }
+}
+
+/// Rotates the [`Sun`] and [`Moon`] from [`TimeOfDay`], so a loaded save restores the sky.
+/// Sun elevation fades light, color, exposure and ambient light between day and night,
+/// and [`Weather`] dims the light on top of that.
 #[allow(clippy::type_complexity)]
 fn sun_cycle(
+    cfg: Res<Config>,
     settings: Res<Settings>,
-    mut sky_lights: Query<&mut Transform, Or<(With<Moon>, With<Sun>)>>,
-    time: Res<Time>,
+    clock: Res<TimeOfDay>,
+    weather: Res<Weather>,
+    mut ambient: ResMut<AmbientLight>,
+    mut exposure: Query<&mut Exposure, With<SceneCamera>>,
+    mut sun: Query<(&mut Transform, &mut DirectionalLight), (With<Sun>, Without<Moon>)>,
+    mut moon: Query<(&mut Transform, &mut DirectionalLight), (With<Moon>, Without<Sun>)>,
 ) {
-    match settings.sun_cycle {
-        SunCycle::DayNight => sky_lights
-            .iter_mut()
-            .for_each(|mut tf| tf.rotate_x(-time.delta_secs() * std::f32::consts::PI / 50.0)),
-        SunCycle::Nimbus => sky_lights
-            .iter_mut()
-            .for_each(|mut tf| tf.rotate_y(-time.delta_secs() * std::f32::consts::PI / 50.0)),
+    let angle = clock.sun_angle();
+    let rotation = match settings.sun_cycle {
+        SunCycle::DayNight => Quat::from_rotation_x(-angle),
+        SunCycle::Nimbus => Quat::from_rotation_y(-angle),
+    };
+    // 0 at night, 1 once the sun is a bit above the horizon
+    let elevation = clock.sun_elevation();
+    let day = smoothstep(-0.1, 0.3, elevation);
+    let night = smoothstep(0.0, 0.3, -elevation);
+    let dim = weather.params(&cfg.weather).light;
+
+    for (mut tf, mut light) in &mut sun {
+        tf.rotation = rotation;
+        light.illuminance = lux::FULL_DAYLIGHT * day * dim;
+        // warm near the horizon, white at noon
+        light.color = SUN.mix(&Color::WHITE, smoothstep(0.1, 0.6, elevation));
+        light.shadows_enabled = day > 0.0;
+    }
+    // the moon is on the opposite side of the sky
+    for (mut tf, mut light) in &mut moon {
+        tf.rotation = rotation * Quat::from_rotation_x(PI);
+        light.illuminance = lux::FULL_MOON_NIGHT * night * dim;
+        light.shadows_enabled = night > 0.0 && day == 0.0;
+    }
+
+    for mut exposure in &mut exposure {
+        exposure.ev100 = NIGHT_EXPOSURE.ev100.lerp(Exposure::OVERCAST.ev100, day);
     }
+    ambient.color = MOON.mix(&Color::WHITE, day);
+    ambient.brightness = NIGHT_AMBIENT.lerp(DAY_AMBIENT, day) * dim;
+}
+
+fn smoothstep(from: f32, to: f32, x: f32) -> f32 {
+    let t = ((x - from) / (to - from)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
 }
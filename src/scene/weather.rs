@@ -0,0 +1,242 @@
+//! Weather on top of the [`SunCycle::Nimbus`] sky: picks the next [`WeatherKind`] on a timer,
+//! blends fog and atmosphere towards it and keeps rain or snow falling around the camera.
+//! Light is dimmed in the sun cycle from the same [`WeatherParams`].
+use super::*;
+use bevy::pbr::{Atmosphere, NotShadowCaster};
+use rand::prelude::*;
+
+/// Particles are kept in a box around the camera this many units to each side
+const PARTICLE_AREA: f32 = 20.0;
+const PARTICLE_HEIGHT: f32 = 25.0;
+/// Caps spawning so a sudden downpour doesn't stall a frame
+const MAX_SPAWN_PER_FRAME: usize = 40;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Startup, setup_particle_assets)
+        .add_systems(OnEnter(Screen::Gameplay), reset_weather)
+        .add_systems(
+            Update,
+            (advance_weather, apply_weather, update_particles)
+                .chain()
+                .run_if(in_state(Screen::Gameplay).and(resource_exists::<Config>)),
+        )
+        .add_observer(force_weather);
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum Precipitation {
+    Rain,
+    Snow,
+}
+
+#[derive(Component)]
+struct WeatherParticle {
+    velocity: Vec3,
+    /// Offsets the snow sway so flakes don't move in lockstep
+    phase: f32,
+}
+
+#[derive(Resource)]
+struct ParticleAssets {
+    rain: (Handle<Mesh>, Handle<StandardMaterial>),
+    snow: (Handle<Mesh>, Handle<StandardMaterial>),
+}
+
+fn setup_particle_assets(
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    // one mesh and material per kind, so all drops render in a single batch
+    let mut unlit = |color: Color| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            unlit: true,
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        })
+    };
+    let rain = unlit(Color::srgba(0.7, 0.75, 0.85, 0.4));
+    let snow = unlit(Color::srgba(1.0, 1.0, 1.0, 0.9));
+    commands.insert_resource(ParticleAssets {
+        rain: (meshes.add(Cuboid::new(0.02, 0.6, 0.02)), rain),
+        snow: (meshes.add(Sphere::new(0.05)), snow),
+    });
+}
+
+/// Every session starts clear and holds it for a while before the first change
+fn reset_weather(cfg: Res<Config>, mut commands: Commands) {
+    commands.insert_resource(Weather {
+        hold: hold_timer(&cfg.weather),
+        ..default()
+    });
+}
+
+fn force_weather(
+    on: Trigger<ForceWeather>,
+    cfg: Res<Config>,
+    settings: Res<Settings>,
+    mut weather: ResMut<Weather>,
+) {
+    if settings.sun_cycle != SunCycle::Nimbus {
+        warn!("weather only changes with the Nimbus sun cycle");
+        return;
+    }
+    info!("forcing weather: {:?}", on.0);
+    weather.transition(on.0, cfg.weather.transition);
+    weather.hold = hold_timer(&cfg.weather);
+}
+
+fn hold_timer(cfg: &WeatherConfig) -> Timer {
+    let max = cfg.max_duration.max(cfg.min_duration);
+    let seconds = thread_rng().gen_range(cfg.min_duration..=max);
+    Timer::from_seconds(seconds, TimerMode::Once)
+}
+
+/// Any weather but the current one, picked by [`WeatherParams::weight`]
+fn pick_next(cfg: &WeatherConfig, current: WeatherKind) -> WeatherKind {
+    let options: Vec<WeatherKind> = WeatherKind::ALL
+        .into_iter()
+        .filter(|k| *k != current)
+        .collect();
+    options
+        .choose_weighted(&mut thread_rng(), |k| cfg.params(*k).weight.max(0.0))
+        .copied()
+        .unwrap_or(current)
+}
+
+fn advance_weather(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut weather: ResMut<Weather>,
+) {
+    let cfg = &cfg.weather;
+    if settings.sun_cycle != SunCycle::Nimbus {
+        weather.transition(WeatherKind::Clear, cfg.transition);
+    } else if weather.hold.tick(time.delta()).just_finished() {
+        let next = pick_next(cfg, weather.current);
+        info!("weather changing to {next:?}");
+        weather.transition(next, cfg.transition);
+        weather.hold = hold_timer(cfg);
+    }
+
+    let mut finished = None;
+    if let Some((next, timer)) = &mut weather.next
+        && timer.tick(time.delta()).finished()
+    {
+        finished = Some(*next);
+    }
+    if let Some(next) = finished {
+        weather.current = next;
+        weather.next = None;
+    }
+}
+
+fn apply_weather(
+    cfg: Res<Config>,
+    weather: Res<Weather>,
+    mut camera: Query<(Option<&mut DistanceFog>, Option<&mut Atmosphere>), With<SceneCamera>>,
+) {
+    let params = weather.params(&cfg.weather);
+    for (fog, atmosphere) in &mut camera {
+        if let Some(mut fog) = fog {
+            let (r, g, b) = params.fog_color;
+            fog.color = Color::srgb(r, g, b);
+            fog.falloff = FogFalloff::ExponentialSquared {
+                density: params.fog_density,
+            };
+        }
+        if let Some(mut atmosphere) = atmosphere {
+            atmosphere.mie_scattering = Atmosphere::EARTH.mie_scattering * params.haze;
+            atmosphere.mie_absorption = Atmosphere::EARTH.mie_absorption * params.haze;
+        }
+    }
+}
+
+/// Spawns or despawns drops to match the weather, then moves them and wraps them around
+/// the camera so the box of particles follows it
+#[allow(clippy::type_complexity)]
+fn update_particles(
+    cfg: Res<Config>,
+    time: Res<Time>,
+    weather: Res<Weather>,
+    assets: Res<ParticleAssets>,
+    camera: Query<&GlobalTransform, With<SceneCamera>>,
+    mut particles: Query<(Entity, &Precipitation, &WeatherParticle, &mut Transform)>,
+    mut commands: Commands,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let center = camera.translation();
+    let params = weather.params(&cfg.weather);
+    let mut rng = thread_rng();
+
+    for (kind, strength) in [
+        (Precipitation::Rain, params.rain),
+        (Precipitation::Snow, params.snow),
+    ] {
+        let wanted = (cfg.weather.particles as f32 * strength.clamp(0.0, 1.0)) as usize;
+        let existing: Vec<Entity> = particles
+            .iter()
+            .filter(|(_, k, ..)| **k == kind)
+            .map(|(e, ..)| e)
+            .collect();
+        for entity in existing.iter().skip(wanted) {
+            commands.entity(*entity).despawn();
+        }
+
+        let (mesh, material) = match kind {
+            Precipitation::Rain => assets.rain.clone(),
+            Precipitation::Snow => assets.snow.clone(),
+        };
+        let missing = wanted
+            .saturating_sub(existing.len())
+            .min(MAX_SPAWN_PER_FRAME);
+        for _ in 0..missing {
+            let offset = Vec3::new(
+                rng.gen_range(-PARTICLE_AREA..PARTICLE_AREA),
+                rng.gen_range(0.0..PARTICLE_HEIGHT),
+                rng.gen_range(-PARTICLE_AREA..PARTICLE_AREA),
+            );
+            let velocity = match kind {
+                Precipitation::Rain => Vec3::new(0.0, -rng.gen_range(20.0..28.0), 0.0),
+                Precipitation::Snow => Vec3::new(0.0, -rng.gen_range(1.0..2.5), 0.0),
+            };
+            commands.spawn((
+                Name::new("Weather Particle"),
+                StateScoped(Screen::Gameplay),
+                kind,
+                WeatherParticle {
+                    velocity,
+                    phase: rng.gen_range(0.0..std::f32::consts::TAU),
+                },
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(center + offset),
+                NotShadowCaster,
+            ));
+        }
+    }
+
+    let dt = time.delta_secs();
+    let t = time.elapsed_secs();
+    for (_, kind, particle, mut tf) in &mut particles {
+        let mut velocity = particle.velocity;
+        if *kind == Precipitation::Snow {
+            velocity.x += (t + particle.phase).sin() * 0.5;
+            velocity.z += (t * 0.7 + particle.phase).cos() * 0.5;
+        }
+        tf.translation += velocity * dt;
+
+        let offset = tf.translation - center;
+        let wrap = |v: f32, min: f32, max: f32| min + (v - min).rem_euclid(max - min);
+        tf.translation = center
+            + Vec3::new(
+                wrap(offset.x, -PARTICLE_AREA, PARTICLE_AREA),
+                wrap(offset.y, -PARTICLE_HEIGHT / 4.0, PARTICLE_HEIGHT),
+                wrap(offset.z, -PARTICLE_AREA, PARTICLE_AREA),
+            );
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::*;
 use bevy::ecs::spawn::SpawnIter;
+use rand::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
@@ -69,23 +70,32 @@ fn grid(content: Vec<[String; 2]>) -> impl Bundle {
     )
 }
 
+/// Plays every stem of a random exploration piece at full volume
 fn start_credits_music(
     mut commands: Commands,
-    settings: Res<Settings>,
-    sources: ResMut<AudioSources>,
+    soundtrack: Res<Soundtrack>,
+    samples: Res<StemSamples>,
     mut music: Query<&mut PlaybackSettings, With<Music>>,
 ) {
     for mut s in music.iter_mut() {
         s.pause();
     }
 
-    let handle = sources.explore[0].clone();
-    commands.spawn((
-        StateScoped(Screen::Credits),
-        Name::new("Credits Music"),
-        Music,
-        SamplePlayer::new(handle)
-            .with_volume(settings.music())
-            .looping(),
-    ));
+    let Some(piece) = soundtrack
+        .pieces(&MoodType::Exploration)
+        .choose(&mut thread_rng())
+    else {
+        return;
+    };
+    for stem in &piece.stems {
+        let Some(handle) = samples.0.get(&stem.sample) else {
+            continue;
+        };
+        commands.spawn((
+            StateScoped(Screen::Credits),
+            Name::new("Credits Music"),
+            Music,
+            SamplePlayer::new(handle.clone()).looping(),
+        ));
+    }
 }
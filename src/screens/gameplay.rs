@@ -4,6 +4,11 @@ use super::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Gameplay), spawn_gameplay_ui)
+        .add_systems(
+            Update,
+            (hide_now_playing, update_stamina_bar).run_if(in_state(Screen::Gameplay)),
+        )
+        .add_observer(show_now_playing)
         .add_observer(toggle_mute)
         .add_observer(toggle_pause)
         .add_observer(trigger_menu_toggle_on_esc)
@@ -36,10 +41,53 @@ fn spawn_gameplay_ui(mut cmds: Commands, textures: Res<Textures>, _settings: Res
                     (icon(opts.clone().image(textures.mute.clone())), MuteIcon),
                 ]
             ),
+            // stamina bar, the fill width follows the player's stamina
+            (
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Px(20.0),
+                    bottom: Px(20.0),
+                    width: Vw(20.0),
+                    height: Px(12.0),
+                    border: UiRect::all(Px(2.0)),
+                    ..Default::default()
+                },
+                BorderColor(GRAY),
+                BorderRadius::all(Px(4.0)),
+                BackgroundColor(TRANSLUCENT),
+                children![(
+                    StaminaBar,
+                    Node {
+                        width: Percent(100.0),
+                        height: Percent(100.0),
+                        ..Default::default()
+                    },
+                    BorderRadius::all(Px(2.0)),
+                    BackgroundColor(LIGHT_BLUE),
+                )],
+            ),
         ],
     ));
 }
 
+/// Scales the fill to the player's [`Stamina`], dimmed while exhausted
+fn update_stamina_bar(
+    stamina: Query<&Stamina, (With<Player>, Changed<Stamina>)>,
+    mut bar: Query<(&mut Node, &mut BackgroundColor), With<StaminaBar>>,
+) {
+    let Ok(stamina) = stamina.single() else {
+        return;
+    };
+    for (mut node, mut color) in &mut bar {
+        node.width = Percent(stamina.fraction() * 100.0);
+        color.0 = if stamina.exhausted {
+            DIM_BLUE
+        } else {
+            LIGHT_BLUE
+        };
+    }
+}
+
 fn toggle_pause(
     _: Trigger<TogglePause>,
     mut time: ResMut<Time<Virtual>>,
@@ -60,47 +108,90 @@ fn toggle_pause(
     info!("paused: {}", state.paused);
 }
 
+/// Briefly shows the piece that just started in the bottom right corner
+fn show_now_playing(
+    on: Trigger<NowPlaying>,
+    cfg: Res<Config>,
+    ui: Query<Entity, With<GameplayUi>>,
+    toasts: Query<Entity, With<NowPlayingToast>>,
+    mut cmds: Commands,
+) {
+    let Ok(ui) = ui.single() else {
+        return;
+    };
+    for toast in &toasts {
+        cmds.entity(toast).despawn();
+    }
+    let opts = Opts::new(format!("Now playing: {}", on.name))
+        .font_size(18.0)
+        .border_radius(Px(8.0))
+        .node(Node {
+            position_type: PositionType::Absolute,
+            right: Px(20.0),
+            bottom: Px(20.0),
+            ..default()
+        })
+        .padding(UiRect::axes(Px(12.0), Px(6.0)));
+    cmds.spawn((
+        label(opts),
+        BackgroundColor(TRANSLUCENT),
+        NowPlayingToast,
+        ToastTimer(Timer::from_seconds(cfg.timers.now_playing, TimerMode::Once)),
+        ChildOf(ui),
+    ));
+}
+
+fn hide_now_playing(
+    time: Res<Time>,
+    mut toasts: Query<(Entity, &mut ToastTimer), With<NowPlayingToast>>,
+    mut cmds: Commands,
+) {
+    for (entity, mut timer) in &mut toasts {
+        if timer.tick(time.delta()).finished() {
+            cmds.entity(entity).despawn();
+        }
+    }
+}
+
+/// Bus volumes follow [`GameState::muted`] in [`apply_volumes`]
 fn toggle_mute(
     _: Trigger<ToggleMute>,
-    settings: ResMut<Settings>,
     mut state: ResMut<GameState>,
     mut label: Query<&mut Node, With<MuteIcon>>,
-    mut music: Single<&mut VolumeNode, (With<SamplerPool<Music>>, Without<SamplerPool<Sfx>>)>,
-    mut sfx: Single<&mut VolumeNode, (With<SamplerPool<Sfx>>, Without<SamplerPool<Music>>)>,
 ) {
+    state.muted = !state.muted;
     if let Ok(mut node) = label.single_mut() {
-        if state.muted {
-            music.volume = settings.music();
-            sfx.volume = settings.sfx();
-            node.display = Display::None;
+        node.display = if state.muted {
+            Display::Flex
         } else {
-            music.volume = Volume::SILENT;
-            sfx.volume = Volume::SILENT;
-            node.display = Display::Flex;
-        }
+            Display::None
+        };
     }
-    state.muted = !state.muted;
     info!("muted: {}", state.muted);
 }
 
 // ============================ UI ============================
 
-fn click_to_menu(
-    on: Trigger<Pointer<Click>>,
-    mut commands: Commands,
-    mut state: ResMut<GameState>,
-) {
+fn click_to_menu(on: Trigger<OnPress>, mut commands: Commands, mut state: ResMut<GameState>) {
     info!("click NewModal target entity: {}", on.target());
     commands.entity(on.target()).insert(ModalCtx);
     commands.trigger(GoTo(Screen::Title));
     state.reset();
 }
-fn click_pop_modal(_: Trigger<Pointer<Click>>, mut cmds: Commands) {
+fn click_pop_modal(_: Trigger<OnPress>, mut cmds: Commands) {
     cmds.trigger(PopModal);
 }
-fn click_spawn_settings(_: Trigger<Pointer<Click>>, mut cmds: Commands) {
+fn click_spawn_settings(_: Trigger<OnPress>, mut cmds: Commands) {
     cmds.trigger(NewModal(Modal::Settings));
 }
+#[cfg(not(target_arch = "wasm32"))]
+fn click_spawn_save(_: Trigger<OnPress>, mut cmds: Commands) {
+    cmds.trigger(NewModal(Modal::Save));
+}
+#[cfg(not(target_arch = "wasm32"))]
+fn click_spawn_load(_: Trigger<OnPress>, mut cmds: Commands) {
+    cmds.trigger(NewModal(Modal::Load));
+}
 
 fn trigger_menu_toggle_on_esc(
     _: Trigger<Back>,
@@ -147,6 +238,10 @@ fn add_new_modal(
     match modal {
         Modal::Main => commands.spawn(menu_modal()),
         Modal::Settings => commands.spawn(settings_modal()),
+        #[cfg(not(target_arch = "wasm32"))]
+        Modal::Save => commands.spawn(slots_modal(SlotAction::Save)),
+        #[cfg(not(target_arch = "wasm32"))]
+        Modal::Load => commands.spawn(slots_modal(SlotAction::Load)),
     };
 
     state.modals.push(modal.clone());
@@ -157,6 +252,7 @@ fn pop_modal(
     screen: Res<State<Screen>>,
     menu_marker: Query<Entity, With<MenuModal>>,
     settings_marker: Query<Entity, With<SettingsModal>>,
+    #[cfg(not(target_arch = "wasm32"))] slots_marker: Query<Entity, With<SlotsModal>>,
     mut commands: Commands,
     mut state: ResMut<GameState>,
 ) {
@@ -180,6 +276,12 @@ fn pop_modal(
                 commands.entity(menu).despawn();
             }
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        Modal::Save | Modal::Load => {
+            if let Ok(menu) = slots_marker.single() {
+                commands.entity(menu).despawn();
+            }
+        }
     }
 
     // respawn next in the modal stack
@@ -187,6 +289,10 @@ fn pop_modal(
         match modal {
             Modal::Main => commands.spawn(menu_modal()),
             Modal::Settings => commands.spawn(settings_modal()),
+            #[cfg(not(target_arch = "wasm32"))]
+            Modal::Save => commands.spawn(slots_modal(SlotAction::Save)),
+            #[cfg(not(target_arch = "wasm32"))]
+            Modal::Load => commands.spawn(slots_modal(SlotAction::Load)),
         };
     }
 
@@ -203,6 +309,7 @@ fn clear_modals(
     state: ResMut<GameState>,
     menu_marker: Query<Entity, With<MenuModal>>,
     settings_marker: Query<Entity, With<SettingsModal>>,
+    #[cfg(not(target_arch = "wasm32"))] slots_marker: Query<Entity, With<SlotsModal>>,
     mut cmds: Commands,
 ) {
     for m in &state.modals {
@@ -217,6 +324,12 @@ fn clear_modals(
                     cmds.entity(modal).despawn();
                 }
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            Modal::Save | Modal::Load => {
+                if let Ok(modal) = slots_marker.single() {
+                    cmds.entity(modal).despawn();
+                }
+            }
         }
     }
 }
@@ -227,6 +340,23 @@ fn settings_modal() -> impl Bundle {
     (StateScoped(Screen::Gameplay), SettingsModal, settings_ui())
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn slots_modal(action: SlotAction) -> impl Bundle {
+    (
+        StateScoped(Screen::Gameplay),
+        SlotsModal,
+        ui_root("Save slots"),
+        BackgroundColor(TRANSLUCENT),
+        children![
+            save_slots(action),
+            btn_small(
+                Opts::new("back").width(Vw(5.0)).border(UiRect::DEFAULT),
+                click_pop_modal
+            )
+        ],
+    )
+}
+
 fn menu_modal() -> impl Bundle {
     let opts = Opts::new("Settings")
         .width(Vw(15.0))
@@ -266,7 +396,16 @@ fn menu_modal() -> impl Bundle {
                         align_content: AlignContent::Center,
                         ..default()
                     },
+                    #[cfg(target_arch = "wasm32")]
+                    children![
+                        btn(opts.clone(), click_spawn_settings),
+                        btn(opts.text("Main Menu"), click_to_menu)
+                    ],
+                    #[cfg(not(target_arch = "wasm32"))]
                     children![
+                        btn(opts.clone().text("Continue"), continue_game),
+                        btn(opts.clone().text("Save"), click_spawn_save),
+                        btn(opts.clone().text("Load"), click_spawn_load),
                         btn(opts.clone(), click_spawn_settings),
                         btn(opts.text("Main Menu"), click_to_menu)
                     ]
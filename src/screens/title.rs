@@ -27,6 +27,7 @@ fn setup_menu(mut commands: Commands, mut state: ResMut<GameState>) {
             // Crutch until we can use #cfg in children![] macro
             // https://github.com/bevyengine/bevy/issues/18953
             #[cfg(target_arch = "wasm32")]
+            // saves are files, so the browser build has no Continue or Load
             children![
                 btn_big("Play", to::gameplay_or_loading),
                 btn_big("Credits", to::credits),
@@ -35,6 +36,8 @@ fn setup_menu(mut commands: Commands, mut state: ResMut<GameState>) {
             #[cfg(not(target_arch = "wasm32"))]
             children![
                 btn_big("Play", to::gameplay_or_loading),
+                btn_big("Continue", continue_game),
+                btn_big("Load", open_load),
                 btn_big("Credits", to::credits),
                 btn_big("Settings", to::settings),
                 btn_big("Exit", exit_app)
@@ -46,6 +49,30 @@ fn setup_menu(mut commands: Commands, mut state: ResMut<GameState>) {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn exit_app(_: Trigger<Pointer<Click>>, mut app_exit: EventWriter<AppExit>) {
+fn open_load(_: Trigger<OnPress>, mut commands: Commands) {
+    commands.spawn((
+        StateScoped(Screen::Title),
+        SlotsModal,
+        ui_root("Load slots"),
+        BackgroundColor(TRANSLUCENT),
+        children![
+            save_slots(SlotAction::Load),
+            btn_small(
+                Opts::new("back").width(Vw(5.0)).border(UiRect::DEFAULT),
+                close_load
+            )
+        ],
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn close_load(_: Trigger<OnPress>, mut commands: Commands, slots: Query<Entity, With<SlotsModal>>) {
+    for slots in slots.iter() {
+        commands.entity(slots).despawn();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn exit_app(_: Trigger<OnPress>, mut app_exit: EventWriter<AppExit>) {
     app_exit.write(AppExit::Success);
 }
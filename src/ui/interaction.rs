@@ -1,15 +1,23 @@
 use super::*;
+use bevy::{input::mouse::AccumulatedMouseMotion, input_focus::InputFocus};
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<UiInteraction>().add_systems(
-        Update,
-        (
-            apply_interaction_palette,
-            (trigger_on_press, btn_sounds).run_if(resource_exists::<AudioSources>),
-        ),
-    );
+    app.register_type::<UiInteraction>()
+        .init_resource::<InputFocus>()
+        .add_systems(
+            Update,
+            (
+                (focus_on_hover, apply_interaction_palette).chain(),
+                (trigger_on_press, btn_sounds).run_if(resource_exists::<AudioSources>),
+            ),
+        )
+        .add_observer(navigate_focus)
+        .add_observer(press_focused);
 }
 
+/// Widgets that [`NavigateModal`] moves the [`InputFocus`] between
+type Focusable = Or<(With<Button>, With<SliderTrack>)>;
+
 /// Palette for widget interactions. Add this to an entity that supports
 /// [`Interaction`]s, such as a button, to change its [`BackgroundColor`]
 /// and [`BorderColor`] based on the current interaction state.
@@ -21,12 +29,15 @@ pub struct UiInteraction {
     pub none: (Color, Color),
     pub hovered: (Color, Color),
     pub pressed: (Color, Color),
+    /// Holds the [`InputFocus`] and is not hovered or pressed
+    pub focused: (Color, Color),
 }
 impl UiInteraction {
     pub const DEFAULT: Self = Self {
         none: (TRANSPARENT, WHITEISH),
         hovered: (LIGHT_BLUE, WHITEISH),
         pressed: (DIM_BLUE, WHITEISH),
+        focused: (TRANSPARENT, LIGHT_BLUE),
     };
     // pub fn all(c: Color) -> Self {
     //     Self {
@@ -51,18 +62,24 @@ impl UiInteraction {
 
 #[allow(clippy::type_complexity)]
 fn apply_interaction_palette(
+    focus: Res<InputFocus>,
     mut palette_query: Query<
         (
-            &Interaction,
+            Entity,
+            Ref<Interaction>,
             &UiInteraction,
             &mut BorderColor,
             &mut BackgroundColor,
         ),
-        (Changed<Interaction>, Without<DisabledButton>),
+        Without<DisabledButton>,
     >,
 ) {
-    for (interaction, palette, mut border_color, mut background) in &mut palette_query {
-        let (bg, border) = match interaction {
+    for (entity, interaction, palette, mut border_color, mut background) in &mut palette_query {
+        if !interaction.is_changed() && !focus.is_changed() {
+            continue;
+        }
+        let (bg, border) = match *interaction {
+            Interaction::None if focus.0 == Some(entity) => palette.focused,
             Interaction::None => palette.none,
             Interaction::Hovered => palette.hovered,
             Interaction::Pressed => palette.pressed,
@@ -72,6 +89,96 @@ fn apply_interaction_palette(
     }
 }
 
+/// The mouse moves the focus too, so keys and gamepad continue from the hovered widget
+fn focus_on_hover(
+    interactions: Query<(Entity, &Interaction), (Changed<Interaction>, Focusable)>,
+    mut focus: ResMut<InputFocus>,
+) {
+    for (entity, interaction) in &interactions {
+        if *interaction != Interaction::None {
+            focus.set(entity);
+        }
+    }
+}
+
+/// Moves the focus to the closest visible widget in the pressed direction,
+/// the first press focuses the top left one
+fn navigate_focus(
+    on: Trigger<Started<NavigateModal>>,
+    mouse: Res<AccumulatedMouseMotion>,
+    mut focus: ResMut<InputFocus>,
+    widgets: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &ComputedNode,
+            &InheritedVisibility,
+        ),
+        Focusable,
+    >,
+    tracks: Query<(), With<SliderTrack>>,
+) {
+    // NavigateModal also listens to the mouse, which moves the focus by hovering
+    if mouse.delta != Vec2::ZERO {
+        return;
+    }
+    let input = on.value;
+    let focused = focus.0.filter(|e| widgets.contains(*e));
+    // focused sliders take left and right for stepping
+    if focused.is_some_and(|e| tracks.contains(e)) && input.x.abs() >= input.y.abs() {
+        return;
+    }
+
+    let visible = widgets
+        .iter()
+        .filter(|(.., node, visibility)| visibility.get() && node.size() != Vec2::ZERO)
+        .map(|(entity, tf, ..)| (entity, tf.translation().truncate()));
+    let Some((_, from, ..)) = focused.and_then(|e| widgets.get(e).ok()) else {
+        if let Some((entity, _)) =
+            visible.min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        {
+            focus.set(entity);
+        }
+        return;
+    };
+
+    // UI y grows downwards
+    let from = from.translation().truncate();
+    let direction = if input.x.abs() >= input.y.abs() {
+        Vec2::new(input.x.signum(), 0.0)
+    } else {
+        Vec2::new(0.0, -input.y.signum())
+    };
+    let next = visible
+        .filter_map(|(entity, pos)| {
+            let offset = pos - from;
+            let along = offset.dot(direction);
+            // prefer widgets in line with the current one
+            let across = offset.perp_dot(direction).abs();
+            (along > 1.0).then_some((entity, along + 2.0 * across))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((entity, _)) = next {
+        focus.set(entity);
+    }
+}
+
+/// [`Select`] presses the focused widget, mouse clicks go through [`Interaction`] instead
+fn press_focused(
+    _: Trigger<Started<Select>>,
+    focus: Res<InputFocus>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    widgets: Query<(), (Focusable, Without<DisabledButton>)>,
+    mut commands: Commands,
+) {
+    if mouse.pressed(MouseButton::Left) {
+        return;
+    }
+    if let Some(entity) = focus.0.filter(|e| widgets.contains(*e)) {
+        commands.trigger_targets(OnPress, entity);
+    }
+}
+
 fn trigger_on_press(
     interaction_query: Query<(Entity, &Interaction), Changed<Interaction>>,
     mut commands: Commands,
@@ -87,7 +194,6 @@ fn trigger_on_press(
 // it's dropping FPS like crazy
 fn btn_sounds(
     mut commands: Commands,
-    settings: Res<Settings>,
     audio_sources: Res<AudioSources>,
     interaction_query: Query<&Interaction, (Changed<Interaction>, Without<DisabledButton>)>,
 ) {
@@ -97,9 +203,7 @@ fn btn_sounds(
             Interaction::Pressed => audio_sources.btn_press.clone(),
             _ => continue,
         };
-        commands.spawn((
-            Sfx,
-            SamplePlayer::new(source.clone()).with_volume(settings.sfx()),
-        ));
+        // the Ui pool carries the ui level
+        commands.spawn((Ui, SamplePlayer::new(source.clone())));
     }
 }
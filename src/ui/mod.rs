@@ -15,5 +15,10 @@ pub use prefabs::*;
 pub use widget::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins((perf::plugin, interaction::plugin, prefabs::plugin));
+    app.add_plugins((
+        perf::plugin,
+        interaction::plugin,
+        widget::plugin,
+        prefabs::plugin,
+    ));
 }
@@ -4,24 +4,34 @@ use bevy::{
         relationship::RelatedSpawner,
         spawn::{SpawnWith, SpawnableList},
     },
-    input::{ButtonState, common_conditions::*, keyboard::KeyboardInput, mouse::MouseButtonInput},
+    input::{
+        ButtonState,
+        common_conditions::*,
+        gamepad::{GamepadAxisChangedEvent, GamepadButtonChangedEvent},
+        keyboard::KeyboardInput,
+        mouse::MouseButtonInput,
+    },
     ui::FocusPolicy,
 };
-use std::fmt::Write;
+use std::fmt::{self, Write};
 
 const GAP: Val = Vw(1.0);
 const DARK_TEXT: TextColor = TextColor(Color::srgb(0.1, 0.1, 0.1));
 const PADDING: UiRect = UiRect::all(Val::Px(15.0));
 const PANEL_BACKGROUND: BackgroundColor = BackgroundColor(Color::srgb(0.8, 0.8, 0.8));
+/// How far a stick has to be pushed to be captured as a half axis binding.
+const AXIS_THRESHOLD: f32 = 0.5;
 
 pub fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            update_button_text,
+            update_button_text::<Binding>,
+            update_button_text::<GamepadBinding>,
             (
                 cancel_binding.run_if(input_just_pressed(KeyCode::Escape)),
                 bind,
+                bind_gamepad,
             )
                 .chain(),
         ),
@@ -55,12 +65,12 @@ pub fn keybind_editor(keybind: &InputSettings) -> impl Bundle {
                         justify_content: JustifyContent::End,
                         ..Default::default()
                     },
-                    children![(
-                        SettingsButton,
-                        Children::spawn(SpawnWith(move |spawner: &mut ChildSpawner| {
-                            spawner.spawn(Text::new("Apply")).observe(apply);
-                        }))
-                    )],
+                    Children::spawn(SpawnWith(|spawner: &mut ChildSpawner| {
+                        // observed on the button itself, `OnPress` targets the focused button
+                        spawner
+                            .spawn((SettingsButton, children![Text::new("Apply")]))
+                            .observe(apply);
+                    })),
                 )
             ]
         )],
@@ -81,7 +91,7 @@ macro_rules! field_name {
     }};
 }
 
-/// Stores name of the [`KeyboardSettings`] field and its array index for which the binding is associated.
+/// Stores name of the [`InputSettings`] field and its array index for which the binding is associated.
 ///
 /// Used to utilize reflection when applying settings.
 #[derive(Component, Clone, Copy)]
@@ -96,62 +106,138 @@ fn actions_grid(keybind: InputSettings) -> impl Bundle {
             display: Display::Grid,
             column_gap: GAP,
             row_gap: GAP,
-            grid_template_columns: vec![GridTrack::auto(); BINDINGS_COUNT + 1],
+            grid_template_columns: vec![
+                GridTrack::auto();
+                BINDINGS_COUNT + GAMEPAD_BINDINGS_COUNT + 1
+            ],
             ..Default::default()
         },
         // We could utilzie reflection to iterate over fields,
         // but in real application you most likely want to have a nice and translatable text on buttons.
         Children::spawn((
-            action_row("Forward", field_name!(keybind.forward), keybind.forward),
-            action_row("Left", field_name!(keybind.left), keybind.left),
-            action_row("Backward", field_name!(keybind.backward), keybind.backward),
-            action_row("Right", field_name!(keybind.right), keybind.right),
-            action_row("Jump", field_name!(keybind.jump), keybind.jump),
-            action_row("Crouch", field_name!(keybind.crouch), keybind.crouch),
-            action_row("Dash", field_name!(keybind.dash), keybind.dash),
-            action_row("Sprint", field_name!(keybind.sprint), keybind.sprint),
+            header_row(),
+            action_row(
+                "Forward",
+                (field_name!(keybind.forward), keybind.forward),
+                (
+                    field_name!(keybind.gamepad.forward),
+                    keybind.gamepad.forward,
+                ),
+            ),
+            action_row(
+                "Left",
+                (field_name!(keybind.left), keybind.left),
+                (field_name!(keybind.gamepad.left), keybind.gamepad.left),
+            ),
+            action_row(
+                "Backward",
+                (field_name!(keybind.backward), keybind.backward),
+                (
+                    field_name!(keybind.gamepad.backward),
+                    keybind.gamepad.backward,
+                ),
+            ),
+            action_row(
+                "Right",
+                (field_name!(keybind.right), keybind.right),
+                (field_name!(keybind.gamepad.right), keybind.gamepad.right),
+            ),
+            action_row(
+                "Jump",
+                (field_name!(keybind.jump), keybind.jump),
+                (field_name!(keybind.gamepad.jump), keybind.gamepad.jump),
+            ),
+            action_row(
+                "Crouch",
+                (field_name!(keybind.crouch), keybind.crouch),
+                (field_name!(keybind.gamepad.crouch), keybind.gamepad.crouch),
+            ),
+            action_row(
+                "Dash",
+                (field_name!(keybind.dash), keybind.dash),
+                (field_name!(keybind.gamepad.dash), keybind.gamepad.dash),
+            ),
+            action_row(
+                "Sprint",
+                (field_name!(keybind.sprint), keybind.sprint),
+                (field_name!(keybind.gamepad.sprint), keybind.gamepad.sprint),
+            ),
+            action_row(
+                "Attack",
+                (field_name!(keybind.attack), keybind.attack),
+                (field_name!(keybind.gamepad.attack), keybind.gamepad.attack),
+            ),
+        )),
+    )
+}
+
+fn header_row() -> impl SpawnableList<ChildOf> {
+    (
+        Spawn((Text::new("Action"), DARK_TEXT)),
+        Spawn((
+            Node {
+                grid_column: GridPlacement::span(BINDINGS_COUNT as u16),
+                ..Default::default()
+            },
+            children![(Text::new("Keyboard & Mouse"), DARK_TEXT)],
+        )),
+        Spawn((
+            Node {
+                grid_column: GridPlacement::span(GAMEPAD_BINDINGS_COUNT as u16),
+                ..Default::default()
+            },
+            children![(Text::new("Gamepad"), DARK_TEXT)],
         )),
     )
 }
 
 fn action_row(
     action_name: &'static str,
-    field_name: &'static str,
-    bindings: [Binding; BINDINGS_COUNT],
+    keys: (&'static str, [Binding; BINDINGS_COUNT]),
+    pads: (&'static str, [GamepadBinding; GAMEPAD_BINDINGS_COUNT]),
 ) -> impl SpawnableList<ChildOf> {
     (
         Spawn((Text::new(action_name), DARK_TEXT)),
-        SpawnWith(move |spawner: &mut ChildSpawner| {
-            for (index, binding) in bindings.into_iter().enumerate() {
-                spawner.spawn((
-                    Node {
-                        column_gap: GAP,
-                        align_items: AlignItems::Center,
-                        ..Default::default()
-                    },
-                    Children::spawn(SpawnWith(move |spawner: &mut ChildSpawner| {
-                        let binding_button = spawner
-                            .spawn((
-                                BindingInfo { field_name, index },
-                                Name::new(action_name),
-                                BindingButton { binding },
-                                children![Text::default()], // Will be updated automatically on `BindingButton` insertion
-                            ))
-                            .observe(show_binding_dialog)
-                            .id();
-                        spawner
-                            .spawn((DeleteButton { binding_button }, children![Text::new("X")]))
-                            .observe(delete_binding);
-                    })),
-                ));
-            }
-        }),
+        binding_cells(action_name, keys.0, keys.1),
+        binding_cells(action_name, pads.0, pads.1),
     )
 }
 
-fn delete_binding(
-    trigger: Trigger<Pointer<Click>>,
-    mut binding_buttons: Query<(&Name, &mut BindingButton)>,
+fn binding_cells<B: BindingKind, const N: usize>(
+    action_name: &'static str,
+    field_name: &'static str,
+    bindings: [B; N],
+) -> impl SpawnableList<ChildOf> {
+    SpawnWith(move |spawner: &mut ChildSpawner| {
+        for (index, binding) in bindings.into_iter().enumerate() {
+            spawner.spawn((
+                Node {
+                    column_gap: GAP,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                Children::spawn(SpawnWith(move |spawner: &mut ChildSpawner| {
+                    let binding_button = spawner
+                        .spawn((
+                            BindingInfo { field_name, index },
+                            Name::new(action_name),
+                            BindingButton { binding },
+                            children![Text::default()], // Will be updated automatically on `BindingButton` insertion
+                        ))
+                        .observe(show_binding_dialog)
+                        .id();
+                    spawner
+                        .spawn((DeleteButton { binding_button }, children![Text::new("X")]))
+                        .observe(delete_binding::<B>);
+                })),
+            ));
+        }
+    })
+}
+
+fn delete_binding<B: BindingKind>(
+    trigger: Trigger<OnPress>,
+    mut binding_buttons: Query<(&Name, &mut BindingButton<B>)>,
     delete_buttons: Query<&DeleteButton>,
 ) {
     let delete_button = delete_buttons.get(trigger.target()).unwrap();
@@ -159,17 +245,23 @@ fn delete_binding(
         .get_mut(delete_button.binding_button)
         .expect("delete button should point to a binding button");
     info!("deleting binding for '{name}'");
-    binding_button.binding = Binding::None;
+    binding_button.binding = B::NONE;
 }
 
 fn show_binding_dialog(
-    trigger: Trigger<Pointer<Click>>,
+    trigger: Trigger<OnPress>,
     mut commands: Commands,
     root_entity: Single<Entity, (With<Node>, Without<ChildOf>)>,
     names: Query<&Name>,
+    gamepad_buttons: Query<(), With<BindingButton<GamepadBinding>>>,
 ) {
     let name = names.get(trigger.target()).unwrap();
     info!("starting binding for '{name}'");
+    let input = if gamepad_buttons.contains(trigger.target()) {
+        "press any gamepad button, push a stick"
+    } else {
+        "press any key"
+    };
 
     commands.entity(*root_entity).with_child((
         BindingDialog {
@@ -189,22 +281,26 @@ fn show_binding_dialog(
                     ..Default::default()
                 },
                 DARK_TEXT,
-                Text::new(format!(
-                    "Binding \"{name}\", \npress any key or Esc to cancel",
-                )),
+                Text::new(format!("Binding \"{name}\", \n{input} or Esc to cancel",)),
             )]
         )],
     ));
 }
 
 fn bind(
-    mut commands: Commands,
+    commands: Commands,
     mut key_events: EventReader<KeyboardInput>,
     mut mouse_button_events: EventReader<MouseButtonInput>,
-    dialog: Single<(Entity, &BindingDialog)>,
+    dialog: Single<(Entity, Ref<BindingDialog>)>,
     root_entity: Single<Entity, (With<Node>, Without<ChildOf>)>,
-    mut buttons: Query<(Entity, &Name, &mut BindingButton)>,
+    buttons: Query<(Entity, &Name, &mut BindingButton<Binding>)>,
 ) {
+    // the press that opened the dialog is not the new binding
+    if dialog.1.is_added() {
+        key_events.clear();
+        mouse_button_events.clear();
+        return;
+    }
     let keys = key_events
         .read()
         .filter(|event| event.state == ButtonState::Pressed)
@@ -218,7 +314,63 @@ fn bind(
         return;
     };
 
-    let (dialog_entity, dialog) = *dialog;
+    assign_binding(
+        commands,
+        (dialog.0, &dialog.1),
+        *root_entity,
+        buttons,
+        binding,
+    );
+}
+
+fn bind_gamepad(
+    commands: Commands,
+    mut button_events: EventReader<GamepadButtonChangedEvent>,
+    mut axis_events: EventReader<GamepadAxisChangedEvent>,
+    dialog: Single<(Entity, Ref<BindingDialog>)>,
+    root_entity: Single<Entity, (With<Node>, Without<ChildOf>)>,
+    buttons: Query<(Entity, &Name, &mut BindingButton<GamepadBinding>)>,
+) {
+    if dialog.1.is_added() {
+        button_events.clear();
+        axis_events.clear();
+        return;
+    }
+    let pressed = button_events
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .map(|event| event.button.into());
+    let pushed = axis_events
+        .read()
+        .filter(|event| event.value.abs() > AXIS_THRESHOLD)
+        .map(|event| GamepadBinding::axis(event.axis, event.value > 0.0));
+
+    let Some(binding) = pressed.chain(pushed).next() else {
+        return;
+    };
+
+    assign_binding(
+        commands,
+        (dialog.0, &dialog.1),
+        *root_entity,
+        buttons,
+        binding,
+    );
+}
+
+/// Assigns captured input to the dialog's button or asks what to do on conflict.
+///
+/// Input of the other kind (e.g. a key while binding a gamepad column) is ignored.
+fn assign_binding<B: BindingKind>(
+    mut commands: Commands,
+    (dialog_entity, dialog): (Entity, &BindingDialog),
+    root_entity: Entity,
+    mut buttons: Query<(Entity, &Name, &mut BindingButton<B>)>,
+    binding: B,
+) {
+    if !buttons.contains(dialog.binding_button) {
+        return;
+    }
 
     if let Some((conflict_button, name, _)) = buttons
         .iter()
@@ -226,7 +378,7 @@ fn bind(
     {
         info!("found conflict with '{name}' for '{binding}'");
 
-        commands.entity(*root_entity).with_child((
+        commands.entity(root_entity).with_child((
             ConflictDialog {
                 binding_button: dialog.binding_button,
                 conflict_button,
@@ -253,7 +405,7 @@ fn bind(
                         Children::spawn(SpawnWith(|spawner: &mut RelatedSpawner<_>| {
                             spawner
                                 .spawn((SettingsButton, children![Text::new("Replace")]))
-                                .observe(replace_binding);
+                                .observe(replace_binding::<B>);
                             spawner
                                 .spawn((SettingsButton, children![Text::new("Cancel")]))
                                 .observe(cancel_replace_binding);
@@ -278,18 +430,18 @@ fn cancel_binding(mut commands: Commands, dialog: Single<Entity, With<BindingDia
     commands.entity(*dialog).despawn();
 }
 
-fn replace_binding(
-    _on: Trigger<Pointer<Click>>,
+fn replace_binding<B: BindingKind>(
+    _on: Trigger<OnPress>,
     mut commands: Commands,
     dialog: Single<(Entity, &ConflictDialog)>,
-    mut buttons: Query<(&Name, &mut BindingButton)>,
+    mut buttons: Query<(&Name, &mut BindingButton<B>)>,
 ) {
     let (dialog_entity, dialog) = *dialog;
     let (_, mut conflict_button) = buttons
         .get_mut(dialog.conflict_button)
         .expect("binding conflict should point to a button");
     let binding = conflict_button.binding;
-    conflict_button.binding = Binding::None;
+    conflict_button.binding = B::NONE;
 
     let (name, mut binding_button) = buttons
         .get_mut(dialog.binding_button)
@@ -301,7 +453,7 @@ fn replace_binding(
 }
 
 fn cancel_replace_binding(
-    _on: Trigger<Pointer<Click>>,
+    _on: Trigger<OnPress>,
     mut commands: Commands,
     dialog: Single<Entity, With<ConflictDialog>>,
 ) {
@@ -310,30 +462,40 @@ fn cancel_replace_binding(
 }
 
 fn apply(
-    _on: Trigger<Pointer<Click>>,
+    _on: Trigger<OnPress>,
     mut commands: Commands,
     mut settings: ResMut<Settings>,
-    buttons: Query<(&BindingButton, &BindingInfo)>,
+    mut store: ResMut<SettingsStore>,
+    buttons: Query<(&BindingButton<Binding>, &BindingInfo)>,
+    gamepad_buttons: Query<(&BindingButton<GamepadBinding>, &BindingInfo)>,
 ) {
     settings.input_map.clear();
     for (button, info) in &buttons {
         // Utilize reflection to write by field name.
         let field_value = settings
+            .input_map
             .path_mut::<[Binding; BINDINGS_COUNT]>(info.field_name)
-            .expect("fields with bindings should be stored as Vec");
+            .expect("fields with bindings should be stored as arrays");
+        field_value[info.index] = button.binding;
+    }
+    for (button, info) in &gamepad_buttons {
+        let field_value = settings
+            .input_map
+            .path_mut::<[GamepadBinding; GAMEPAD_BINDINGS_COUNT]>(info.field_name)
+            .expect("fields with bindings should be stored as arrays");
         field_value[info.index] = button.binding;
     }
 
     commands.trigger(SettingsChanged);
 
-    match settings.save() {
-        Ok(()) => info!("writing settings to '{SETTINGS_PATH}'"),
-        Err(e) => error!("unable to write settings to '{SETTINGS_PATH}': {e}"),
+    match settings.save(store.0.as_mut()) {
+        Ok(()) => info!("writing settings to '{}'", store.location()),
+        Err(e) => error!("unable to write settings to '{}': {e}", store.location()),
     }
 }
 
-fn update_button_text(
-    buttons: Query<(&BindingButton, &Children), Changed<BindingButton>>,
+fn update_button_text<B: BindingKind>(
+    buttons: Query<(&BindingButton<B>, &Children), Changed<BindingButton<B>>>,
     mut text: Query<&mut Text>,
 ) {
     for (button, children) in &buttons {
@@ -356,20 +518,6 @@ fn update_button_background(
     }
 }
 
-fn reload_bindings(
-    _on: Trigger<SettingsChanged>,
-    // settings: Res<Settings>,
-    mut commands: Commands,
-    mut player: Query<Entity, With<Player>>,
-) {
-    if let Ok(player) = player.single_mut() {
-        commands
-            .entity(player)
-            .despawn_related::<Actions<Player>>()
-            .insert(PlayerCtx);
-    }
-}
-
 #[derive(Component, Default)]
 #[require(
     Button,
@@ -386,9 +534,22 @@ struct SettingsButton;
 /// Button associated with a binding.
 #[derive(Component)]
 #[require(SettingsButton)]
-struct BindingButton {
+struct BindingButton<B: BindingKind> {
     /// Assigned binding.
-    binding: Binding,
+    binding: B,
+}
+
+/// Input stored by a [`BindingButton`], keyboard and gamepad columns go through the same dialogs.
+trait BindingKind: Copy + PartialEq + fmt::Display + Send + Sync + 'static {
+    const NONE: Self;
+}
+
+impl BindingKind for Binding {
+    const NONE: Self = Binding::None;
+}
+
+impl BindingKind for GamepadBinding {
+    const NONE: Self = GamepadBinding::None;
 }
 
 /// Button that clears the associated [`BindingButton`].
@@ -1,9 +1,13 @@
 use super::*;
 
 mod keybind_editor;
+#[cfg(not(target_arch = "wasm32"))]
+mod save_slots;
 mod settings;
 
 pub use keybind_editor::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use save_slots::*;
 pub use settings::*;
 
 pub fn plugin(app: &mut App) {
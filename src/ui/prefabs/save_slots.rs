@@ -0,0 +1,66 @@
+//! Save slot list, shared by the in-game menu and the title screen
+use super::*;
+use bevy::ecs::spawn::SpawnWith;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotAction {
+    Save,
+    Load,
+}
+
+/// Column with a button per [`SAVE_SLOTS`] entry that saves to or loads from that slot,
+/// loading also lists the [`AUTOSAVE_SLOT`]
+pub fn save_slots(action: SlotAction) -> impl Bundle {
+    let saves = SaveData::list();
+    let opts = Opts::default()
+        .width(Vw(25.0))
+        .padding(UiRect::axes(Vw(2.0), Vw(0.5)));
+    let title = match action {
+        SlotAction::Save => "Save game",
+        SlotAction::Load => "Load game",
+    };
+
+    (
+        Name::new("Save Slots"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Vh(2.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(header(title));
+            // autosave can be loaded, but not overwritten by hand
+            let autosave = (action == SlotAction::Load).then_some(AUTOSAVE_SLOT);
+            for slot in SAVE_SLOTS.into_iter().chain(autosave) {
+                let text = match saves.iter().find(|s| s.slot == slot) {
+                    Some(save) => format!("{slot}: {}", save.age()),
+                    None => format!("{slot}: empty"),
+                };
+                let opts = opts.clone().text(text);
+                match action {
+                    SlotAction::Save => {
+                        parent.spawn(btn(opts, move |_: Trigger<OnPress>, mut cmds: Commands| {
+                            cmds.trigger(SaveGame(slot.to_string()));
+                            cmds.trigger(PopModal);
+                        }))
+                    }
+                    SlotAction::Load => {
+                        parent.spawn(btn(opts, move |_: Trigger<OnPress>, mut cmds: Commands| {
+                            cmds.trigger(LoadGame(slot.to_string()));
+                            cmds.trigger(PopModal);
+                        }))
+                    }
+                };
+            }
+        })),
+    )
+}
+
+/// Loads the most recent save, if there is any
+pub fn continue_game(_: Trigger<OnPress>, mut cmds: Commands) {
+    match SaveData::latest() {
+        Some(slot) => cmds.trigger(LoadGame(slot)),
+        None => info!("no saves to continue from"),
+    }
+}
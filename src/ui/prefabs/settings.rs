@@ -1,19 +1,197 @@
 use super::*;
+use bevy::ecs::spawn::SpawnWith;
 #[cfg(feature = "dev_native")]
 use bevy::ui::Display as NodeDisplay;
-use bevy::window::{PresentMode, PrimaryWindow};
+use bevy::window::{Monitor, PrimaryMonitor};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            update_general_volume_label,
-            update_music_volume_label,
-            update_sfx_volume_label,
-            update_fov_label,
             update_tab_content.run_if(resource_changed::<ActiveTab>),
+            (sync_setting_sliders, update_option_labels).run_if(resource_changed::<Settings>),
         ),
-    );
+    )
+    .add_observer(init_setting_slider)
+    .add_observer(switch_tab);
+}
+
+/// What a settings [`Slider`] controls
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+enum SettingSlider {
+    General,
+    Music,
+    Sfx,
+    Ambience,
+    Ui,
+    Fov,
+    RenderScale,
+    UiScale,
+}
+
+impl SettingSlider {
+    fn value(self, settings: &Settings) -> f32 {
+        match self {
+            Self::General => settings.sound.general,
+            Self::Music => settings.sound.music,
+            Self::Sfx => settings.sound.sfx,
+            Self::Ambience => settings.sound.ambience,
+            Self::Ui => settings.sound.ui,
+            Self::Fov => settings.fov,
+            Self::RenderScale => settings.graphics.render_scale,
+            Self::UiScale => settings.display.ui_scale,
+        }
+    }
+}
+
+/// [`DisplaySettings`] option a Video tab button cycles through
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+enum DisplayOption {
+    Mode,
+    Resolution,
+    Vsync,
+    FrameCap,
+}
+
+impl DisplayOption {
+    const ALL: [Self; 4] = [Self::Mode, Self::Resolution, Self::Vsync, Self::FrameCap];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Mode => "Window mode",
+            Self::Resolution => "Resolution",
+            Self::Vsync => "VSync",
+            Self::FrameCap => "Frame cap",
+        }
+    }
+
+    fn value(self, display: &DisplaySettings) -> String {
+        match self {
+            Self::Mode => display.mode.to_string(),
+            Self::Resolution => match display.resolution {
+                Some(size) => format!("{}x{}", size.x, size.y),
+                None => "auto".to_string(),
+            },
+            Self::Vsync => if display.vsync { "on" } else { "off" }.to_string(),
+            Self::FrameCap => match display.frame_cap {
+                Some(fps) => fps.to_string(),
+                None => "off".to_string(),
+            },
+        }
+    }
+
+    /// Steps the option, resolutions go from the largest of `resolutions` down and back to auto
+    fn cycle(self, display: &mut DisplaySettings, resolutions: &[UVec2]) {
+        match self {
+            Self::Mode => display.mode = display.mode.next(),
+            Self::Resolution => {
+                let options: Vec<Option<UVec2>> = std::iter::once(None)
+                    .chain(resolutions.iter().copied().map(Some))
+                    .collect();
+                display.resolution = next_option(&options, display.resolution);
+            }
+            Self::Vsync => display.vsync = !display.vsync,
+            Self::FrameCap => display.frame_cap = next_option(&FRAME_CAPS, display.frame_cap),
+        }
+    }
+}
+
+/// Option after `current`, wrapping around
+fn next_option<T: Copy + PartialEq>(options: &[Option<T>], current: Option<T>) -> Option<T> {
+    let i = options.iter().position(|o| *o == current);
+    options[i.map_or(0, |i| (i + 1) % options.len())]
+}
+
+/// [`Graphics`] option a Video tab button cycles through
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+enum GraphicsOption {
+    Preset,
+    Shadows,
+    Atmosphere,
+    Antialiasing,
+    Bloom,
+    Fog,
+}
+
+impl GraphicsOption {
+    const ALL: [Self; 6] = [
+        Self::Preset,
+        Self::Shadows,
+        Self::Atmosphere,
+        Self::Antialiasing,
+        Self::Bloom,
+        Self::Fog,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Preset => "Quality",
+            Self::Shadows => "Shadows",
+            Self::Atmosphere => "Atmosphere",
+            Self::Antialiasing => "Anti-aliasing",
+            Self::Bloom => "Bloom",
+            Self::Fog => "Fog",
+        }
+    }
+
+    fn value(self, graphics: &Graphics) -> String {
+        let on_off = |on| if on { "on" } else { "off" }.to_string();
+        match self {
+            Self::Preset => graphics.preset.to_string(),
+            Self::Shadows => graphics.shadows.to_string(),
+            Self::Atmosphere => graphics.atmosphere.to_string(),
+            Self::Antialiasing => graphics.antialiasing.to_string(),
+            Self::Bloom => on_off(graphics.bloom),
+            Self::Fog => on_off(graphics.fog),
+        }
+    }
+
+    /// Steps the option, picking a preset replaces every option at once
+    fn cycle(self, graphics: &mut Graphics) {
+        match self {
+            Self::Preset => *graphics = graphics.preset.next().graphics(),
+            Self::Shadows => graphics.shadows = graphics.shadows.next(),
+            Self::Atmosphere => graphics.atmosphere = graphics.atmosphere.next(),
+            Self::Antialiasing => graphics.antialiasing = graphics.antialiasing.next(),
+            Self::Bloom => graphics.bloom = !graphics.bloom,
+            Self::Fog => graphics.fog = !graphics.fog,
+        }
+        if self != Self::Preset {
+            graphics.update_preset();
+        }
+    }
+}
+
+/// [`Controls`] modifier a Keybindings tab button switches between hold and toggle
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+enum ControlOption {
+    Sprint,
+    Crouch,
+}
+
+impl ControlOption {
+    const ALL: [Self; 2] = [Self::Sprint, Self::Crouch];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sprint => "Sprint",
+            Self::Crouch => "Crouch",
+        }
+    }
+
+    fn mode(self, controls: &mut Controls) -> &mut ModifierMode {
+        match self {
+            Self::Sprint => &mut controls.sprint,
+            Self::Crouch => &mut controls.crouch,
+        }
+    }
+
+    fn value(self, controls: &Controls) -> String {
+        match self {
+            Self::Sprint => controls.sprint.to_string(),
+            Self::Crouch => controls.crouch.to_string(),
+        }
+    }
 }
 
 // ============================ CONTROL KNOBS OBSERVERS ============================
@@ -21,13 +199,14 @@ pub(super) fn plugin(app: &mut App) {
 pub fn save_settings(
     _: Trigger<OnPress>,
     settings: Res<Settings>,
+    mut store: ResMut<SettingsStore>,
     root: Query<&Children, With<SaveSettingsLabel>>,
     children_q: Query<&Children>,
     mut text_q: Query<&mut Text>,
 ) {
-    match settings.save() {
+    match settings.save(store.0.as_mut()) {
         Ok(()) => {
-            info!("writing settings to '{SETTINGS_PATH}'");
+            info!("writing settings to '{}'", store.location());
             if let Ok(children) = root.single() {
                 for child in children.iter() {
                     if let Ok(grandchildren) = children_q.get(child) {
@@ -40,11 +219,22 @@ pub fn save_settings(
                 }
             }
         }
-        Err(e) => error!("unable to write settings to '{SETTINGS_PATH}': {e}"),
+        Err(e) => error!("unable to write settings to '{}': {e}", store.location()),
     }
 }
 
 // TAB CHANGING
+/// Only while the settings are open, the tab keys do nothing elsewhere
+fn switch_tab(
+    on: Trigger<SwitchTab>,
+    tab_bar: Query<(), With<TabBar>>,
+    mut active_tab: ResMut<ActiveTab>,
+) {
+    if !tab_bar.is_empty() {
+        active_tab.0 = on.0;
+    }
+}
+
 fn update_tab_content(
     settings: Res<Settings>,
     active_tab: Res<ActiveTab>,
@@ -68,13 +258,21 @@ fn update_tab_content(
                             commands.spawn(audio_grid()).insert(ChildOf(e));
                         }
                         UiTab::Video => {
-                            commands
-                                .spawn(video_grid(&settings.sun_cycle))
-                                .insert(ChildOf(e));
+                            commands.spawn(video_grid(&settings)).insert(ChildOf(e));
                         }
                         UiTab::Keybindings => {
                             commands
-                                .spawn(keybind_editor(&settings.input_map))
+                                .spawn((
+                                    Node {
+                                        flex_direction: FlexDirection::Column,
+                                        row_gap: Px(30.0),
+                                        ..default()
+                                    },
+                                    children![
+                                        controls_grid(settings.controls),
+                                        keybind_editor(&settings.input_map)
+                                    ],
+                                ))
                                 .insert(ChildOf(e));
                         }
                     }
@@ -88,165 +286,178 @@ fn update_tab_content(
     Ok(())
 }
 
-// ============================ +/- BUTTON HOOKS ============================
+// ============================ SLIDERS ============================
 
-fn lower_fov(
-    _: Trigger<Pointer<Click>>,
+fn init_setting_slider(
+    on: Trigger<OnAdd, SettingSlider>,
     cfg: Res<Config>,
-    mut settings: ResMut<Settings>,
-    mut world_model_projection: Single<&mut Projection>,
+    settings: Res<Settings>,
+    mut sliders: Query<(&SettingSlider, &mut Slider)>,
 ) {
-    let Projection::Perspective(perspective) = world_model_projection.as_mut() else {
+    let Ok((kind, mut slider)) = sliders.get_mut(on.target()) else {
         return;
     };
-    let new_fov = (settings.fov - cfg.settings.step.to_degrees()).max(cfg.settings.min_fov);
-    perspective.fov = new_fov.to_radians();
-    settings.fov = perspective.fov.to_degrees();
-}
-
-fn raise_fov(
-    _: Trigger<Pointer<Click>>,
-    cfg: Res<Config>,
-    mut settings: ResMut<Settings>,
-    mut world_model_projection: Single<&mut Projection>,
-) {
-    let Projection::Perspective(perspective) = world_model_projection.as_mut() else {
-        return;
+    let bounds = &cfg.settings;
+    let value = kind.value(&settings);
+    *slider = match kind {
+        SettingSlider::General
+        | SettingSlider::Music
+        | SettingSlider::Sfx
+        | SettingSlider::Ambience
+        | SettingSlider::Ui => Slider {
+            value,
+            min: bounds.min_volume,
+            max: bounds.max_volume,
+            step: bounds.step,
+            ..default()
+        },
+        SettingSlider::Fov => Slider {
+            value,
+            min: bounds.min_fov,
+            max: bounds.max_fov,
+            step: bounds.step.to_degrees(),
+            format: |v| format!("{v:.0}"),
+        },
+        SettingSlider::RenderScale => Slider {
+            value,
+            min: 0.5,
+            max: 1.0,
+            step: 0.05,
+            ..default()
+        },
+        SettingSlider::UiScale => Slider {
+            value,
+            min: 0.5,
+            max: 2.0,
+            step: 0.1,
+            ..default()
+        },
     };
-    let new_fov = (settings.fov + cfg.settings.step.to_degrees()).min(cfg.settings.max_fov);
-    perspective.fov = new_fov.to_radians();
-    settings.fov = perspective.fov.to_degrees();
-}
-
-fn update_fov_label(settings: Res<Settings>, mut label: Single<&mut Text, With<FovLabel>>) {
-    let fov = settings.fov.round();
-    let text = format!("{fov: <3}"); // pad to 3 chars
-    label.0 = text;
-}
-
-// GENERAL
-fn lower_general(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut general: Single<&mut VolumeNode, With<MainBus>>,
-) {
-    let new_volume = (settings.sound.general - cfg.settings.step).max(cfg.settings.min_volume);
-    settings.sound.general = new_volume;
-    general.volume = Volume::Linear(new_volume);
-}
-
-fn raise_general(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut general: Single<&mut VolumeNode, With<MainBus>>,
-) {
-    let new_volume = (settings.sound.general + cfg.settings.step).min(cfg.settings.max_volume);
-    settings.sound.general = new_volume;
-    general.volume = Volume::Linear(new_volume);
 }
 
-fn update_general_volume_label(
+/// Settings can change without the slider, e.g. picking a graphics preset
+fn sync_setting_sliders(
     settings: Res<Settings>,
-    mut label: Single<&mut Text, With<GeneralVolumeLabel>>,
+    mut sliders: Query<(&SettingSlider, &mut Slider)>,
 ) {
-    let percent = (settings.sound.general * 100.0).round();
-    let text = format!("{percent: <3}%"); // pad the percent to 3 chars
-    label.0 = text;
+    for (kind, mut slider) in &mut sliders {
+        let value = kind.value(&settings);
+        if slider.value != value {
+            slider.value = value;
+        }
+    }
 }
 
-// MUSIC
-fn lower_music(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
+/// Applies slider changes right away, so they can be heard and seen before saving
+fn apply_setting_slider(
+    on: Trigger<SliderChanged>,
+    sliders: Query<&SettingSlider>,
     mut settings: ResMut<Settings>,
-    mut music: Single<&mut VolumeNode, With<SamplerPool<Music>>>,
+    mut projection: Query<&mut Projection>,
 ) {
-    let new_volume = (settings.sound.music - cfg.settings.step).max(cfg.settings.min_volume);
-    settings.sound.music = new_volume;
-    music.volume = settings.music();
+    let Ok(kind) = sliders.get(on.target()) else {
+        return;
+    };
+    let value = on.0;
+    match kind {
+        // bus volumes follow in `apply_volumes`
+        SettingSlider::General => settings.sound.general = value,
+        SettingSlider::Music => settings.sound.music = value,
+        SettingSlider::Sfx => settings.sound.sfx = value,
+        SettingSlider::Ambience => settings.sound.ambience = value,
+        SettingSlider::Ui => settings.sound.ui = value,
+        SettingSlider::RenderScale => {
+            settings.graphics.render_scale = value;
+            settings.graphics.update_preset();
+        }
+        SettingSlider::UiScale => settings.display.ui_scale = value,
+        SettingSlider::Fov => {
+            settings.fov = value;
+            for mut projection in &mut projection {
+                if let Projection::Perspective(perspective) = projection.as_mut() {
+                    perspective.fov = value.to_radians();
+                }
+            }
+        }
+    }
 }
 
-fn raise_music(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut music: Single<&mut VolumeNode, With<SamplerPool<Music>>>,
-) {
-    let new_volume = (settings.sound.music + cfg.settings.step).min(cfg.settings.max_volume);
-    settings.sound.music = new_volume;
-    music.volume = settings.music();
-}
+// ============================ OTHER BUTTON HOOKS ============================
 
-fn update_music_volume_label(
-    settings: Res<Settings>,
-    mut label: Single<&mut Text, With<MusicVolumeLabel>>,
-) {
-    let percent = (settings.sound.music * 100.0).round();
-    let text = format!("{percent: <3}%"); // pad the percent to 3 chars
-    label.0 = text;
+fn cycle_graphics(option: GraphicsOption) -> impl Fn(Trigger<OnPress>, ResMut<Settings>) + Clone {
+    move |_: Trigger<OnPress>, mut settings: ResMut<Settings>| {
+        option.cycle(&mut settings.graphics);
+        info!(
+            "{} set to {}",
+            option.name(),
+            option.value(&settings.graphics)
+        );
+    }
 }
 
-// SFX
-fn lower_sfx(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut sfx: Single<&mut VolumeNode, With<SamplerPool<Sfx>>>,
-) {
-    let new_volume = (settings.sound.sfx - cfg.settings.step).max(cfg.settings.min_volume);
-    settings.sound.sfx = new_volume;
-    sfx.volume = settings.sfx();
+fn cycle_display(
+    option: DisplayOption,
+) -> impl Fn(Trigger<OnPress>, ResMut<Settings>, Query<&Monitor, With<PrimaryMonitor>>) + Clone {
+    move |_: Trigger<OnPress>,
+          mut settings: ResMut<Settings>,
+          monitor: Query<&Monitor, With<PrimaryMonitor>>| {
+        let sizes = monitor.single().map(resolutions).unwrap_or_default();
+        option.cycle(&mut settings.display, &sizes);
+        info!(
+            "{} set to {}",
+            option.name(),
+            option.value(&settings.display)
+        );
+    }
 }
 
-fn raise_sfx(
-    _: Trigger<Pointer<Click>>,
-    cfg: ResMut<Config>,
-    mut settings: ResMut<Settings>,
-    mut sfx: Single<&mut VolumeNode, With<SamplerPool<Sfx>>>,
-) {
-    let new_volume = (settings.sound.sfx + cfg.settings.step).min(cfg.settings.max_volume);
-    settings.sound.sfx = new_volume;
-    sfx.volume = settings.sfx();
+fn cycle_control(option: ControlOption) -> impl Fn(Trigger<OnPress>, ResMut<Settings>) + Clone {
+    move |_: Trigger<OnPress>, mut settings: ResMut<Settings>| {
+        let mode = option.mode(&mut settings.controls);
+        *mode = mode.next();
+        info!("{} set to {}", option.name(), mode);
+    }
 }
 
-fn update_sfx_volume_label(
-    mut label: Single<&mut Text, With<SfxVolumeLabel>>,
+fn update_option_labels(
     settings: Res<Settings>,
+    graphics: Query<(Entity, &GraphicsOption)>,
+    display: Query<(Entity, &DisplayOption)>,
+    controls: Query<(Entity, &ControlOption)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
 ) {
-    let percent = (settings.sound.sfx * 100.0).round();
-    let text = format!("{percent: <3}%"); // pad the percent to 3 chars
-    label.0 = text;
-}
-
-// ============================ OTHER BUTTON HOOKS ============================
-
-fn switch_to_tab(tab: UiTab) -> impl Fn(Trigger<Pointer<Click>>, ResMut<ActiveTab>) + Clone {
-    move |_: Trigger<Pointer<Click>>, mut active_tab: ResMut<ActiveTab>| {
-        active_tab.0 = tab;
+    let values = graphics
+        .iter()
+        .map(|(entity, option)| (entity, option.value(&settings.graphics)))
+        .chain(
+            display
+                .iter()
+                .map(|(entity, option)| (entity, option.value(&settings.display))),
+        )
+        .chain(
+            controls
+                .iter()
+                .map(|(entity, option)| (entity, option.value(&settings.controls))),
+        );
+    for (entity, value) in values {
+        for child in children.iter_descendants(entity) {
+            if let Ok(mut text) = texts.get_mut(child) {
+                text.0 = value.clone();
+            }
+        }
     }
 }
 
-fn click_toggle_vsync(
-    _: Trigger<Pointer<Click>>,
-    mut windows: Query<&mut Window, With<PrimaryWindow>>,
-) -> Result {
-    for mut window in windows.iter_mut() {
-        if matches!(window.present_mode, PresentMode::AutoVsync) {
-            window.present_mode = PresentMode::AutoNoVsync;
-        } else {
-            window.present_mode = PresentMode::AutoVsync;
-        }
-        info!(" window present_mode changed to: {:?}", window.present_mode);
+fn switch_to_tab(tab: UiTab) -> impl Fn(Trigger<OnPress>, ResMut<ActiveTab>) + Clone {
+    move |_: Trigger<OnPress>, mut active_tab: ResMut<ActiveTab>| {
+        active_tab.0 = tab;
     }
-
-    Ok(())
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 fn click_toggle_diagnostics(
-    _: Trigger<Pointer<Click>>,
+    _: Trigger<OnPress>,
     mut commands: Commands,
     mut state: ResMut<GameState>,
     mut perf_ui: Query<&mut Node, With<PerfUi>>,
@@ -275,7 +486,7 @@ fn click_toggle_diagnostics(
 
 #[cfg(not(target_arch = "wasm32"))]
 fn clock_toggle_debug_ui(
-    _: Trigger<Pointer<Click>>,
+    _: Trigger<OnPress>,
     mut commands: Commands,
     mut state: ResMut<GameState>,
     mut label: Query<&mut Text, With<DiagnosticsLabel>>,
@@ -294,7 +505,7 @@ fn clock_toggle_debug_ui(
 }
 
 fn click_toggle_sun_cycle(
-    _: Trigger<Pointer<Click>>,
+    _: Trigger<OnPress>,
     labels: Query<&Children, With<SunCycleLabel>>,
     mut texts: Query<&mut Text>,
     mut settings: ResMut<Settings>,
@@ -412,9 +623,25 @@ fn bottom_row() -> impl Bundle {
     )
 }
 
-fn video_grid(cycle: &SunCycle) -> impl Bundle {
+fn video_grid(settings: &Settings) -> impl Bundle {
+    (
+        Name::new("Settings Video"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(30.0),
+            ..default()
+        },
+        children![
+            window_grid(&settings.display),
+            display_grid(&settings.sun_cycle),
+            graphics_grid(&settings.graphics)
+        ],
+    )
+}
+
+fn display_grid(cycle: &SunCycle) -> impl Bundle {
     (
-        Name::new("Settings Video Grid"),
+        Name::new("Settings Display Grid"),
         Node {
             row_gap: Px(10.0),
             column_gap: Px(30.0),
@@ -429,20 +656,14 @@ fn video_grid(cycle: &SunCycle) -> impl Bundle {
             label("Sun cycle"),
             (btn(cycle.as_str(), click_toggle_sun_cycle), SunCycleLabel),
             label("FOV"),
-            fov(),
-            // TODO: do checkboxes when feathers
-            label("VSync"),
-            (btn("on", click_toggle_vsync), VsyncLabel),
+            setting_slider(SettingSlider::Fov),
         ],
         #[cfg(not(target_arch = "wasm32"))]
         children![
             label("Sun cycle"),
             (btn(cycle.as_str(), click_toggle_sun_cycle), SunCycleLabel),
             label("FOV"),
-            fov(),
-            // TODO: do checkboxes when feathers
-            label("VSync"),
-            (btn("on", click_toggle_vsync), VsyncLabel),
+            setting_slider(SettingSlider::Fov),
             label("diagnostics"),
             (btn("on", click_toggle_diagnostics), DiagnosticsLabel),
             label("debug ui"),
@@ -451,91 +672,100 @@ fn video_grid(cycle: &SunCycle) -> impl Bundle {
     )
 }
 
-fn audio_grid() -> impl Bundle {
+fn window_grid(display: &DisplaySettings) -> impl Bundle {
+    let values = DisplayOption::ALL.map(|option| (option, option.value(display)));
     (
-        Name::new("Settings Grid"),
+        Name::new("Settings Window Grid"),
         Node {
             row_gap: Px(10.0),
             column_gap: Px(30.0),
             display: Display::Grid,
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            grid_template_columns: RepeatedGridTrack::vw(4, 20.0),
+            justify_items: JustifyItems::Center,
+            align_items: AlignItems::Center,
             ..default()
         },
-        children![
-            label("general"),
-            general_volume(),
-            label("music"),
-            music_volume(),
-            label("sfx"),
-            sfx_volume(),
-        ],
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (option, value) in values {
+                parent.spawn(label(option.name()));
+                parent.spawn((btn(value, cycle_display(option)), option));
+            }
+            parent.spawn(label("UI scale"));
+            parent.spawn(setting_slider(SettingSlider::UiScale));
+        })),
     )
 }
 
-fn general_volume() -> impl Bundle {
+fn graphics_grid(graphics: &Graphics) -> impl Bundle {
+    let values = GraphicsOption::ALL.map(|option| (option, option.value(graphics)));
     (
+        Name::new("Settings Graphics Grid"),
         Node {
-            justify_self: JustifySelf::Center,
-            ..Default::default()
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::vw(4, 20.0),
+            justify_items: JustifyItems::Center,
+            align_items: AlignItems::Center,
+            ..default()
         },
-        children![
-            btn_small("-", lower_general),
-            knob_label(GeneralVolumeLabel),
-            btn_small("+", raise_general),
-        ],
-    )
-}
-
-// TODO: fov slider when feathers
-fn fov() -> impl Bundle {
-    (
-        knobs_container(),
-        children![
-            btn_small("-", lower_fov),
-            knob_label(FovLabel),
-            btn_small("+", raise_fov),
-        ],
-    )
-}
-
-fn music_volume() -> impl Bundle {
-    (
-        knobs_container(),
-        children![
-            btn_small("-", lower_music),
-            knob_label(MusicVolumeLabel),
-            btn_small("+", raise_music),
-        ],
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (option, value) in values {
+                parent.spawn(label(option.name()));
+                parent.spawn((btn(value, cycle_graphics(option)), option));
+            }
+            parent.spawn(label("Render scale"));
+            parent.spawn(setting_slider(SettingSlider::RenderScale));
+        })),
     )
 }
 
-fn sfx_volume() -> impl Bundle {
+fn controls_grid(controls: Controls) -> impl Bundle {
     (
-        knobs_container(),
-        children![
-            btn_small("-", lower_sfx),
-            knob_label(SfxVolumeLabel),
-            btn_small("+", raise_sfx),
-        ],
+        Name::new("Settings Controls Grid"),
+        Node {
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::vw(4, 20.0),
+            justify_items: JustifyItems::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for option in ControlOption::ALL {
+                parent.spawn(label(option.name()));
+                parent.spawn((btn(option.value(&controls), cycle_control(option)), option));
+            }
+        })),
     )
 }
 
-fn knob_label(knob: impl Component) -> impl Bundle {
+fn audio_grid() -> impl Bundle {
     (
+        Name::new("Settings Grid"),
         Node {
-            padding: UiRect::horizontal(Px(10.0)),
-            justify_content: JustifyContent::Center,
-            ..Default::default()
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            ..default()
         },
-        children![(label(""), knob)],
+        children![
+            label("general"),
+            setting_slider(SettingSlider::General),
+            label("music"),
+            setting_slider(SettingSlider::Music),
+            label("sfx"),
+            setting_slider(SettingSlider::Sfx),
+            label("ambience"),
+            setting_slider(SettingSlider::Ambience),
+            label("ui"),
+            setting_slider(SettingSlider::Ui),
+        ],
     )
 }
 
-fn knobs_container() -> impl Bundle {
-    Node {
-        justify_self: JustifySelf::Center,
-        align_content: AlignContent::SpaceEvenly,
-        min_width: Px(100.0),
-        ..Default::default()
-    }
+fn setting_slider(kind: SettingSlider) -> impl Bundle {
+    (slider(apply_setting_slider), kind)
 }
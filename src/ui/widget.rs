@@ -1,11 +1,191 @@
 //! Helper functions for creating common widgets.
 
 use super::*;
-use bevy::ecs::{spawn::SpawnWith, system::IntoObserverSystem};
+use bevy::{
+    ecs::{spawn::SpawnWith, system::IntoObserverSystem},
+    input::mouse::AccumulatedMouseMotion,
+    input_focus::InputFocus,
+    ui::RelativeCursorPosition,
+};
 use std::borrow::Cow;
 
 pub const BORDER_RADIUS: f32 = 15.0;
 pub const FONT_SIZE: f32 = 24.0;
+const SLIDER_WIDTH: f32 = 200.0;
+const THUMB_SIZE: f32 = 16.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, (drag_slider, update_slider).chain())
+        .add_observer(step_slider);
+}
+
+/// Value of a [`slider`], kept within `min..=max` on multiples of `step` from `min`
+#[derive(Component, Clone, Debug)]
+pub struct Slider {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+    /// Text next to the track
+    pub format: fn(f32) -> String,
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Self {
+            value: 0.0,
+            min: 0.0,
+            max: 1.0,
+            step: 0.1,
+            format: |v| format!("{:.0}%", v * 100.0),
+        }
+    }
+}
+
+impl Slider {
+    fn snap(&self, value: f32) -> f32 {
+        let steps = ((value - self.min) / self.step).round();
+        (self.min + steps * self.step).clamp(self.min, self.max)
+    }
+
+    /// Position of the value on the track, 0 to 1
+    fn fraction(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A horizontal slider with its value next to it. Drag the track with the mouse or focus it
+/// and step it left and right with [`NavigateModal`].
+///
+/// Every change triggers [`SliderChanged`] on the slider, `action` observes it.
+pub fn slider<B, M, I>(action: I) -> impl Bundle
+where
+    B: Bundle,
+    I: IntoObserverSystem<SliderChanged, B, M>,
+{
+    let action = IntoObserverSystem::into_system(action);
+    (
+        Name::new("Slider"),
+        Slider::default(),
+        Node {
+            justify_self: JustifySelf::Center,
+            align_items: AlignItems::Center,
+            column_gap: Px(20.0),
+            ..default()
+        },
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent.spawn(Observer::new(action).with_entity(parent.target_entity()));
+            parent.spawn((
+                Name::new("Slider Track"),
+                SliderTrack,
+                Interaction::default(),
+                RelativeCursorPosition::default(),
+                Node {
+                    width: Px(SLIDER_WIDTH),
+                    height: Px(THUMB_SIZE / 2.0),
+                    border: UiRect::all(Px(1.0)),
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BorderRadius::all(Px(THUMB_SIZE)),
+                BorderColor(WHITEISH),
+                BackgroundColor(DIM_BLUE),
+                children![(
+                    SliderThumb,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Px(THUMB_SIZE),
+                        height: Px(THUMB_SIZE),
+                        margin: UiRect::left(Px(-THUMB_SIZE / 2.0)),
+                        ..default()
+                    },
+                    BorderRadius::MAX,
+                    BackgroundColor(WHITEISH),
+                    Pickable::IGNORE,
+                )],
+            ));
+            parent.spawn((label(""), SliderLabel));
+        })),
+    )
+}
+
+fn set_slider(commands: &mut Commands, entity: Entity, slider: &mut Slider, value: f32) {
+    let value = slider.snap(value);
+    if value != slider.value {
+        slider.value = value;
+        commands.trigger_targets(SliderChanged(value), entity);
+    }
+}
+
+fn drag_slider(
+    tracks: Query<(&Interaction, &RelativeCursorPosition, &ChildOf), With<SliderTrack>>,
+    mut sliders: Query<&mut Slider>,
+    mut commands: Commands,
+) {
+    for (interaction, cursor, child_of) in &tracks {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let (Some(cursor), Ok(mut slider)) =
+            (cursor.normalized, sliders.get_mut(child_of.parent()))
+        else {
+            continue;
+        };
+        let value = slider.min + cursor.x.clamp(0.0, 1.0) * (slider.max - slider.min);
+        set_slider(&mut commands, child_of.parent(), &mut slider, value);
+    }
+}
+
+fn step_slider(
+    on: Trigger<Started<NavigateModal>>,
+    mouse: Res<AccumulatedMouseMotion>,
+    focus: Res<InputFocus>,
+    tracks: Query<&ChildOf, With<SliderTrack>>,
+    mut sliders: Query<&mut Slider>,
+    mut commands: Commands,
+) {
+    // NavigateModal also listens to the mouse, which drags sliders instead
+    let direction = on.value;
+    if mouse.delta != Vec2::ZERO || direction.x.abs() < direction.y.abs() {
+        return;
+    }
+    let Some(child_of) = focus.0.and_then(|e| tracks.get(e).ok()) else {
+        return;
+    };
+    let Ok(mut slider) = sliders.get_mut(child_of.parent()) else {
+        return;
+    };
+    let value = slider.value + slider.step * direction.x.signum();
+    set_slider(&mut commands, child_of.parent(), &mut slider, value);
+}
+
+fn update_slider(
+    focus: Res<InputFocus>,
+    sliders: Query<(Entity, Ref<Slider>)>,
+    children: Query<&Children>,
+    mut thumbs: Query<(&mut Node, &mut BackgroundColor, &ChildOf), With<SliderThumb>>,
+    mut labels: Query<&mut Text, With<SliderLabel>>,
+) {
+    for (entity, slider) in &sliders {
+        if !slider.is_changed() && !focus.is_changed() {
+            continue;
+        }
+        for child in children.iter_descendants(entity) {
+            if let Ok((mut node, mut color, track)) = thumbs.get_mut(child) {
+                node.left = Percent(slider.fraction() * 100.0);
+                let focused = focus.0 == Some(track.parent());
+                color.0 = if focused { LIGHT_BLUE } else { WHITEISH };
+            }
+            if let Ok(mut text) = labels.get_mut(child) {
+                text.0 = (slider.format)(slider.value);
+            }
+        }
+    }
+}
 
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {